@@ -0,0 +1,34 @@
+//! Runtime API definition for the Market module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The `too_many_arguments` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::too_many_arguments)]
+// The `unnecessary_mut_passed` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+use sp_runtime::FixedU128;
+
+sp_api::decl_runtime_apis! {
+	pub trait MarketApi<CurrencyId, Balance, BlockNumber> where
+		CurrencyId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+	{
+		/// The time-weighted average price of one whole unit of
+		/// `currency_a` in whole units of `currency_b`, over the last
+		/// `window` blocks.
+		fn twap(currency_a: CurrencyId, currency_b: CurrencyId, window: BlockNumber) -> Option<FixedU128>;
+
+		/// The balance of the stability buffer of `currency_id`.
+		fn stability_buffer(currency_id: CurrencyId) -> Balance;
+
+		/// The stability buffer of `currency_id` relative to its total
+		/// issuance.
+		fn stability_buffer_coverage(currency_id: CurrencyId) -> Option<FixedU128>;
+
+		/// The price `currency_id` is pegged to, derived from the market
+		/// prices of its constituents if it is a basket.
+		fn peg_price(currency_id: CurrencyId) -> Option<FixedU128>;
+	}
+}
@@ -1,4 +1,8 @@
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 2.0.0
+//! Default weights for the Market module.
+//!
+//! Only the transfer and `update_balance` weights come from the Substrate
+//! benchmark CLI. The others are estimates and must be replaced with
+//! benchmarked weights before use in a production runtime.
 
 #![allow(unused_parens)]
 #![allow(unused_imports)]
@@ -26,4 +30,251 @@ impl crate::WeightInfo for () {
 	fn update_balance_native_currency_killing() -> Weight {
 		(62_595_000 as Weight)
 	}
+	fn expand_supply() -> Weight {
+		(195_712_000 as Weight)
+			.saturating_add(DbWeight::get().reads(7 as Weight))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
+	}
+	fn contract_supply() -> Weight {
+		(188_304_000 as Weight)
+			.saturating_add(DbWeight::get().reads(7 as Weight))
+			.saturating_add(DbWeight::get().writes(4 as Weight))
+	}
+	fn set_peg_target() -> Weight {
+		(21_004_000 as Weight)
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn on_initialize(c: u32) -> Weight {
+		(2_135_000 as Weight)
+			.saturating_add((201_380_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().reads((9 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(DbWeight::get().writes((6 as Weight).saturating_mul(c as Weight)))
+	}
+	fn set_supply_change_limit() -> Weight {
+		(23_517_000 as Weight)
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn pause_currency() -> Weight {
+		(18_263_000 as Weight)
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn unpause_currency() -> Weight {
+		(18_571_000 as Weight)
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn set_native_transfers_paused() -> Weight {
+		(16_940_000 as Weight)
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn global_settle() -> Weight {
+		(31_822_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn redeem() -> Weight {
+		(164_386_000 as Weight)
+			.saturating_add(DbWeight::get().reads(6 as Weight))
+			.saturating_add(DbWeight::get().writes(4 as Weight))
+	}
+	fn swap() -> Weight {
+		(221_478_000 as Weight)
+			.saturating_add(DbWeight::get().reads(9 as Weight))
+			.saturating_add(DbWeight::get().writes(6 as Weight))
+	}
+	fn recover_swap_pools(c: u32) -> Weight {
+		(1_206_000 as Weight)
+			.saturating_add((6_712_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
+	fn add_liquidity() -> Weight {
+		(246_925_000 as Weight)
+			.saturating_add(DbWeight::get().reads(9 as Weight))
+			.saturating_add(DbWeight::get().writes(7 as Weight))
+	}
+	fn remove_liquidity() -> Weight {
+		(239_114_000 as Weight)
+			.saturating_add(DbWeight::get().reads(9 as Weight))
+			.saturating_add(DbWeight::get().writes(7 as Weight))
+	}
+	fn swap_exact_in() -> Weight {
+		(208_637_000 as Weight)
+			.saturating_add(DbWeight::get().reads(7 as Weight))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
+	}
+	fn swap_exact_out() -> Weight {
+		(209_082_000 as Weight)
+			.saturating_add(DbWeight::get().reads(7 as Weight))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
+	}
+	fn place_order(m: u32) -> Weight {
+		(118_540_000 as Weight)
+			.saturating_add((96_238_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().reads((5 as Weight).saturating_mul(m as Weight)))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
+			.saturating_add(DbWeight::get().writes((6 as Weight).saturating_mul(m as Weight)))
+	}
+	fn cancel_order() -> Weight {
+		(74_615_000 as Weight)
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(4 as Weight))
+	}
+	fn expire_orders(c: u32) -> Weight {
+		(1_873_000 as Weight)
+			.saturating_add((72_394_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(DbWeight::get().reads((3 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(DbWeight::get().writes((4 as Weight).saturating_mul(c as Weight)))
+	}
+	fn add_feeder() -> Weight {
+		(31_264_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn remove_feeder() -> Weight {
+		(30_871_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn feed_price(c: u32) -> Weight {
+		(48_392_000 as Weight)
+			.saturating_add((6_127_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn bond_feeder() -> Weight {
+		(52_418_000 as Weight)
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn unbond_feeder() -> Weight {
+		(49_736_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn withdraw_feeder_bond() -> Weight {
+		(47_213_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn commit_price_vote() -> Weight {
+		(34_102_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn reveal_price_vote() -> Weight {
+		(41_855_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn tally_price_votes(c: u32) -> Weight {
+		(5_214_000 as Weight)
+			.saturating_add((88_406_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().reads((6 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(DbWeight::get().writes((5 as Weight).saturating_mul(c as Weight)))
+	}
+	fn set_pid_gains() -> Weight {
+		(29_473_000 as Weight)
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn set_peg_band() -> Weight {
+		(20_816_000 as Weight)
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn buy_bonds(b: u32) -> Weight {
+		(86_309_000 as Weight)
+			.saturating_add((4_102_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(DbWeight::get().reads(9 as Weight))
+			.saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(b as Weight)))
+			.saturating_add(DbWeight::get().writes(7 as Weight))
+			.saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(b as Weight)))
+	}
+	fn set_seigniorage_distribution(b: u32) -> Weight {
+		(24_538_000 as Weight)
+			.saturating_add((1_286_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn stake() -> Weight {
+		(98_127_000 as Weight)
+			.saturating_add(DbWeight::get().reads(8 as Weight))
+			.saturating_add(DbWeight::get().writes(6 as Weight))
+	}
+	fn unstake() -> Weight {
+		(96_482_000 as Weight)
+			.saturating_add(DbWeight::get().reads(8 as Weight))
+			.saturating_add(DbWeight::get().writes(6 as Weight))
+	}
+	fn claim_staking_rewards() -> Weight {
+		(71_905_000 as Weight)
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn set_savings_rate() -> Weight {
+		(34_690_000 as Weight)
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn deposit_savings() -> Weight {
+		(88_251_000 as Weight)
+			.saturating_add(DbWeight::get().reads(7 as Weight))
+			.saturating_add(DbWeight::get().writes(6 as Weight))
+	}
+	fn withdraw_savings() -> Weight {
+		(89_734_000 as Weight)
+			.saturating_add(DbWeight::get().reads(7 as Weight))
+			.saturating_add(DbWeight::get().writes(6 as Weight))
+	}
+	fn set_stability_buffer_shares() -> Weight {
+		(31_904_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn set_serp_mode() -> Weight {
+		(30_817_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn bid_auction() -> Weight {
+		(96_402_000 as Weight)
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().writes(6 as Weight))
+	}
+	fn settle_auctions(c: u32) -> Weight {
+		(4_318_000 as Weight)
+			.saturating_add((71_529_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(DbWeight::get().reads((4 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(DbWeight::get().writes((5 as Weight).saturating_mul(c as Weight)))
+	}
+	fn take_auction() -> Weight {
+		(83_165_000 as Weight)
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
+	}
+	fn mint_sett() -> Weight {
+		(79_263_000 as Weight)
+			.saturating_add(DbWeight::get().reads(8 as Weight))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
+	}
+	fn redeem_sett() -> Weight {
+		(80_931_000 as Weight)
+			.saturating_add(DbWeight::get().reads(8 as Weight))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
+	}
+	fn swap_sett() -> Weight {
+		(74_582_000 as Weight)
+			.saturating_add(DbWeight::get().reads(9 as Weight))
+			.saturating_add(DbWeight::get().writes(4 as Weight))
+	}
+	fn set_basket(c: u32, p: u32) -> Weight {
+		(36_128_000 as Weight)
+			.saturating_add((3_604_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+			.saturating_add(DbWeight::get().writes((2 as Weight).saturating_mul(c as Weight)))
+	}
 }
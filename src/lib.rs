@@ -3,7 +3,6 @@
 
 use codec::Codec;
 use frame_support::{
-	debug::native,
 	pallet_prelude::*,
 	traits::{
 		Currency as SetheumCurrency, ExistenceRequirement, Get, 
@@ -15,13 +14,17 @@ use frame_system::{ensure_root, ensure_signed, pallet_prelude::*};
 use serp_traits::{
 	account::MergeAccount,
 	arithmetic::{Signed, SimpleArithmetic},
-	BalanceStatus, SerpMarket, Stp258Asset, Stp258AssetExtended, Stp258AssetLockable, Stp258AssetReservable,
+	BalanceStatus, GetByKey, SerpMarket, Stp258Asset, Stp258AssetExtended, Stp258AssetLockable, Stp258AssetReservable,
 	LockIdentifier, Stp258Currency, Stp258CurrencyExtended, Stp258CurrencyReservable, Stp258CurrencyLockable,
 };
 use orml_utilities::with_transaction_result;
 use sp_runtime::{
-	traits::{CheckedSub, MaybeSerializeDeserialize, StaticLookup, Zero},
-	DispatchError, DispatchResult,
+	helpers_128bit::multiply_by_rational,
+	traits::{
+		AccountIdConversion, CheckedDiv, CheckedMul, CheckedSub, Hash as HashT, IntegerSquareRoot,
+		MaybeSerializeDeserialize, One, SaturatedConversion, Saturating, StaticLookup, Zero,
+	},
+	DispatchError, DispatchResult, FixedI128, FixedPointNumber, FixedU128, ModuleId, PerThing, Perbill,
 };
 use sp_std::{
 	convert::{TryFrom, TryInto},
@@ -35,6 +38,271 @@ mod tests;
 
 pub use module::*;
 
+/// Limits on how fast the supply of a currency may be changed by the SERP.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct SupplyChangeLimit<BlockNumber> {
+	/// The maximum single expansion or contraction, relative to the total
+	/// issuance.
+	pub max_single_change: Perbill,
+	/// The maximum cumulative expansion and contraction within the
+	/// trailing window, relative to the current total issuance.
+	pub max_window_change: Perbill,
+	/// The length of the trailing window, in blocks. Must not be zero.
+	pub window: BlockNumber,
+}
+
+/// The gains of the PID controller computing the automatic supply
+/// adjustments of a currency, applied to the price error relative to the
+/// peg target.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct PidGains {
+	/// The proportional gain.
+	pub proportional: FixedI128,
+	/// The integral gain.
+	pub integral: FixedI128,
+	/// The derivative gain.
+	pub derivative: FixedI128,
+	/// The bound on the absolute value of the accumulated error, keeping
+	/// the integral term from winding up.
+	pub integral_limit: FixedI128,
+}
+
+/// The state of the PID controller of a currency, carried between
+/// adjustment periods.
+#[derive(Encode, Decode, Clone, Copy, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct PidState {
+	/// The price error accumulated over the previous adjustment periods.
+	pub integral: FixedI128,
+	/// The price error of the previous adjustment period.
+	pub last_error: FixedI128,
+}
+
+/// The shares of the seigniorage and swap fees of a settcurrency paid into
+/// its stability buffer. Market transfers carry no fee, so the spread fees
+/// of swaps are the fees shared with the buffer; the mint and redeem fees
+/// and the Tobin tax of settcurrencies are paid into it whole.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct BufferShares {
+	/// The share of the seigniorage of supply expansions.
+	pub seigniorage: Perbill,
+	/// The share of the spread fees of swaps paid in the settcurrency.
+	pub fees: Perbill,
+}
+
+/// The side of a limit order.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum OrderSide {
+	/// Buy the settcurrency with the native currency.
+	Bid,
+	/// Sell the settcurrency for the native currency.
+	Ask,
+}
+
+impl OrderSide {
+	/// The side orders on this side are matched against.
+	pub fn opposite(self) -> Self {
+		match self {
+			OrderSide::Bid => OrderSide::Ask,
+			OrderSide::Ask => OrderSide::Bid,
+		}
+	}
+}
+
+/// The id of a limit order.
+pub type OrderId = u64;
+
+/// A limit order between the native currency and a settcurrency.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Order<AccountId, CurrencyId, Balance, BlockNumber> {
+	/// The account that placed the order.
+	pub owner: AccountId,
+	/// The settcurrency traded.
+	pub currency_id: CurrencyId,
+	/// Whether the order buys or sells the settcurrency.
+	pub side: OrderSide,
+	/// The limit price, in native currency per `base_unit` of the
+	/// settcurrency.
+	pub price: Balance,
+	/// The amount of the settcurrency left to fill.
+	pub amount: Balance,
+	/// The amount still reserved for the order, native currency for bids
+	/// and settcurrency for asks.
+	pub reserved: Balance,
+	/// The block at which the order expires.
+	pub expires_at: BlockNumber,
+}
+
+/// The id of a bond.
+pub type BondId = u64;
+
+/// A bond bought by burning a settcurrency below its peg, redeemed 1:1 in
+/// the settcurrency out of future supply expansions.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Bond<AccountId, CurrencyId, Balance, BlockNumber> {
+	/// The account the bond is redeemed to.
+	pub owner: AccountId,
+	/// The settcurrency of the bond.
+	pub currency_id: CurrencyId,
+	/// The amount of the settcurrency left to redeem.
+	pub amount: Balance,
+	/// The block after which the bond is no longer redeemed, if any.
+	pub expires_at: Option<BlockNumber>,
+}
+
+/// How the price of a descending price auction falls.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum PriceDecay {
+	/// The price falls by `decay` of the starting price every block.
+	Linear,
+	/// The price falls by `decay` of the previous price every block.
+	Exponential,
+}
+
+/// The price curve of a descending price auction.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct DutchCurve {
+	/// The premium on the oracle price the auction starts at.
+	pub start_premium: Perbill,
+	/// The part of the price the price falls by every block.
+	pub decay: Perbill,
+	/// How the price falls.
+	pub kind: PriceDecay,
+	/// The lowest price the auction sells at, relative to the oracle
+	/// price at its start. Must not be zero.
+	pub min_price: Perbill,
+}
+
+/// How the SERP sources the supply contractions and distributes the
+/// supply expansions of a settcurrency.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum SerpMode {
+	/// The supply is contracted from and expanded to the serper.
+	Direct,
+	/// The supply changes are run through auctions.
+	Auction,
+	/// The supply changes are run through descending price auctions
+	/// following the curve.
+	Dutch(DutchCurve),
+	/// The supply is contracted by offering bonds, burned as they are
+	/// bought, and expanded to the serper.
+	Bonds,
+}
+
+impl Default for SerpMode {
+	fn default() -> Self {
+		SerpMode::Direct
+	}
+}
+
+/// The kind of a SERP supply auction.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum AuctionKind {
+	/// Newly minted settcurrency is sold for native currency, which is
+	/// burned. The highest native currency bid wins.
+	Expansion,
+	/// Settcurrency is bought for newly minted native currency, and
+	/// burned. The lowest native currency ask wins.
+	Contraction,
+}
+
+/// The id of a SERP supply auction.
+pub type AuctionId = u64;
+
+/// A SERP supply auction.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Auction<AccountId, CurrencyId, Balance, BlockNumber> {
+	/// The settcurrency auctioned.
+	pub currency_id: CurrencyId,
+	/// Whether the auction expands or contracts the supply.
+	pub kind: AuctionKind,
+	/// The amount of the settcurrency minted or burned.
+	pub lot: Balance,
+	/// The lowest native currency bid accepted in an expansion auction, or
+	/// the highest native currency ask accepted in a contraction auction,
+	/// worth the lot at the oracle price.
+	pub reserve: Balance,
+	/// The best bid so far and its bidder, in native currency.
+	pub bid: Option<(AccountId, Balance)>,
+	/// The block at which the auction started.
+	pub start: BlockNumber,
+	/// The block at which the auction ends.
+	pub end: BlockNumber,
+}
+
+/// A descending price SERP supply auction, taken in slices of the lot.
+///
+/// The price of an expansion auction is the native currency paid per unit
+/// of the settcurrency minted, and the price of a contraction auction the
+/// settcurrency burned per unit of the native currency minted.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct DutchAuction<CurrencyId, Balance, BlockNumber> {
+	/// The settcurrency auctioned.
+	pub currency_id: CurrencyId,
+	/// Whether the auction expands or contracts the supply.
+	pub kind: AuctionKind,
+	/// The amount of the settcurrency left to mint or burn.
+	pub lot: Balance,
+	/// The price at the start of the auction.
+	pub start_price: Price,
+	/// The lowest price the auction sells at.
+	pub floor_price: Price,
+	/// The curve the price falls along.
+	pub curve: DutchCurve,
+	/// The block the auction started at.
+	pub start: BlockNumber,
+	/// The block at which the auction ends.
+	pub end: BlockNumber,
+}
+
+/// A price, as a fixed point number.
+pub type Price = FixedU128;
+
+/// An observation of the price of a currency pair, weighted by the volume
+/// of the trades of the pair in a block.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct PriceObservation<BlockNumber, Balance> {
+	/// The block of the observation.
+	pub at: BlockNumber,
+	/// The sum of the price over every block before `at`.
+	pub cumulative: Price,
+	/// The volume-weighted average price of the trades in block `at`.
+	pub price: Price,
+	/// The volume of the trades in block `at`, in the first currency of
+	/// the pair.
+	pub volume: Balance,
+}
+
+/// A feed of market prices for the SERP.
+pub trait PriceProvider<CurrencyId> {
+	/// The price of one whole unit (one `base_unit`) of `currency_id`,
+	/// denominated in the unit of account settcurrencies are pegged to.
+	fn get_price(currency_id: CurrencyId) -> Option<Price>;
+}
+
+/// The direction of a SERP supply adjustment.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum SerpDirection {
+	/// The supply was expanded.
+	Expansion,
+	/// The supply was contracted.
+	Contraction,
+}
+
+/// A record of a single SERP supply adjustment.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct SupplyAdjustment<BlockNumber, Balance> {
+	/// The block in which the adjustment happened.
+	pub at: BlockNumber,
+	/// Whether the supply was expanded or contracted.
+	pub direction: SerpDirection,
+	/// The amount the supply was adjusted by.
+	pub amount: Balance,
+	/// The quote price the adjustment was serped at.
+	pub quote_price: Balance,
+	/// The total issuance of the currency after the adjustment.
+	pub total_issuance: Balance,
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -45,6 +313,53 @@ pub mod module {
 		fn update_balance_non_native_currency() -> Weight;
 		fn update_balance_native_currency_creating() -> Weight;
 		fn update_balance_native_currency_killing() -> Weight;
+		fn expand_supply() -> Weight;
+		fn contract_supply() -> Weight;
+		fn set_peg_target() -> Weight;
+		fn on_initialize(c: u32) -> Weight;
+		fn set_supply_change_limit() -> Weight;
+		fn pause_currency() -> Weight;
+		fn unpause_currency() -> Weight;
+		fn set_native_transfers_paused() -> Weight;
+		fn global_settle() -> Weight;
+		fn redeem() -> Weight;
+		fn swap() -> Weight;
+		fn recover_swap_pools(c: u32) -> Weight;
+		fn add_liquidity() -> Weight;
+		fn remove_liquidity() -> Weight;
+		fn swap_exact_in() -> Weight;
+		fn swap_exact_out() -> Weight;
+		fn place_order(m: u32) -> Weight;
+		fn cancel_order() -> Weight;
+		fn expire_orders(c: u32) -> Weight;
+		fn add_feeder() -> Weight;
+		fn remove_feeder() -> Weight;
+		fn feed_price(c: u32) -> Weight;
+		fn bond_feeder() -> Weight;
+		fn unbond_feeder() -> Weight;
+		fn withdraw_feeder_bond() -> Weight;
+		fn commit_price_vote() -> Weight;
+		fn reveal_price_vote() -> Weight;
+		fn tally_price_votes(c: u32) -> Weight;
+		fn set_pid_gains() -> Weight;
+		fn set_peg_band() -> Weight;
+		fn buy_bonds(b: u32) -> Weight;
+		fn set_seigniorage_distribution(b: u32) -> Weight;
+		fn stake() -> Weight;
+		fn unstake() -> Weight;
+		fn claim_staking_rewards() -> Weight;
+		fn set_savings_rate() -> Weight;
+		fn deposit_savings() -> Weight;
+		fn withdraw_savings() -> Weight;
+		fn set_stability_buffer_shares() -> Weight;
+		fn set_serp_mode() -> Weight;
+		fn bid_auction() -> Weight;
+		fn settle_auctions(c: u32) -> Weight;
+		fn take_auction() -> Weight;
+		fn mint_sett() -> Weight;
+		fn redeem_sett() -> Weight;
+		fn swap_sett() -> Weight;
+		fn set_basket(c: u32, p: u32) -> Weight;
 	}
 
 	pub(crate) type BalanceOf<T> =
@@ -53,6 +368,14 @@ pub mod module {
 		<<T as Config>::Stp258Currency as Stp258Currency<<T as frame_system::Config>::AccountId>>::CurrencyId;
 	pub(crate) type AmountOf<T> =
 		<<T as Config>::Stp258Currency as Stp258CurrencyExtended<<T as frame_system::Config>::AccountId>>::Amount;
+	pub(crate) type OrderOf<T> =
+		Order<<T as frame_system::Config>::AccountId, CurrencyIdOf<T>, BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
+	pub(crate) type BondOf<T> =
+		Bond<<T as frame_system::Config>::AccountId, CurrencyIdOf<T>, BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
+	pub(crate) type AuctionOf<T> =
+		Auction<<T as frame_system::Config>::AccountId, CurrencyIdOf<T>, BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
+	pub(crate) type DutchAuctionOf<T> =
+		DutchAuction<CurrencyIdOf<T>, BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
@@ -71,6 +394,197 @@ pub mod module {
 		#[pallet::constant]
 		type GetStp258NativeId: Get<CurrencyIdOf<Self>>;
 
+		/// The origin which may trigger supply expansion and contraction.
+		type SerpOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The maximum number of supply adjustments kept in the history of
+		/// each currency.
+		#[pallet::constant]
+		type MaxSupplyHistory: Get<u32>;
+
+		/// The oracle of the market prices used by the SERP.
+		type Oracle: PriceProvider<CurrencyIdOf<Self>>;
+
+		/// The number of blocks between automatic supply adjustments.
+		#[pallet::constant]
+		type AdjustmentPeriod: Get<Self::BlockNumber>;
+
+		/// The maximum number of currencies serped automatically in a single
+		/// block, and so the maximum number of currencies with a peg target
+		/// or a basket.
+		#[pallet::constant]
+		type MaxAutoSerpsPerBlock: Get<u32>;
+
+		/// The origin which may pause and unpause currencies.
+		type PauseOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The account holding the native currency paid out to holders of
+		/// globally settled currencies.
+		#[pallet::constant]
+		type SettlementReserveAccount: Get<Self::AccountId>;
+
+		/// The serper account, which receives the spread fees of swaps.
+		#[pallet::constant]
+		type GetSerperAcc: Get<Self::AccountId>;
+
+		/// The liquidity of the virtual swap pool of each settcurrency, in
+		/// whole units of the settcurrency.
+		#[pallet::constant]
+		type SwapBasePool: Get<BalanceOf<Self>>;
+
+		/// The number of blocks it takes a swap pool to recover to balance.
+		#[pallet::constant]
+		type PoolRecoveryPeriod: Get<Self::BlockNumber>;
+
+		/// The minimum spread charged on swaps.
+		#[pallet::constant]
+		type MinSwapSpread: Get<Perbill>;
+
+		/// The Market module id, used to derive the accounts holding
+		/// liquidity pool reserves.
+		#[pallet::constant]
+		type ModuleId: Get<ModuleId>;
+
+		/// The fee charged on liquidity pool swaps, paid to liquidity
+		/// providers.
+		#[pallet::constant]
+		type PoolSwapFee: Get<Perbill>;
+
+		/// The maximum number of resting orders on each side of the order
+		/// book of a settcurrency.
+		#[pallet::constant]
+		type MaxOrdersPerSide: Get<u32>;
+
+		/// The maximum number of price observations kept for each currency
+		/// pair, bounding the longest time-weighted average price window.
+		#[pallet::constant]
+		type MaxPriceObservations: Get<u32>;
+
+		/// The origin which may add and remove oracle feeders.
+		type FeederOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The feeder account prices fed by the root origin are recorded
+		/// under.
+		type RootOperatorAccountId: Get<Self::AccountId>;
+
+		/// The maximum number of oracle feeders.
+		#[pallet::constant]
+		type MaxFeeders: Get<u32>;
+
+		/// The number of blocks after which a fed price is stale.
+		#[pallet::constant]
+		type MaxPriceAge: Get<Self::BlockNumber>;
+
+		/// The maximum deviation of a fed price from the fresh accepted
+		/// price of its currency.
+		#[pallet::constant]
+		type MaxPriceDeviation: Get<Perbill>;
+
+		/// The minimum number of fresh fed prices of a currency needed to
+		/// accept their median.
+		#[pallet::constant]
+		type MinFeeds: Get<u32>;
+
+		/// The number of blocks in a price vote period. Votes committed in
+		/// one period are revealed in the next, and tallied when it ends.
+		#[pallet::constant]
+		type VotePeriod: Get<Self::BlockNumber>;
+
+		/// The minimum native currency bond of a feeder to vote on prices.
+		#[pallet::constant]
+		type MinFeederBond: Get<BalanceOf<Self>>;
+
+		/// The number of blocks an unbonded feeder bond stays reserved, and
+		/// slashable, before it can be withdrawn.
+		#[pallet::constant]
+		type UnbondingPeriod: Get<Self::BlockNumber>;
+
+		/// The distance from the weighted median, relative to it, within
+		/// which a price vote is rewarded.
+		#[pallet::constant]
+		type RewardBand: Get<Perbill>;
+
+		/// The number of missed price votes after which a feeder is
+		/// slashed.
+		#[pallet::constant]
+		type MaxMissedVotes: Get<u32>;
+
+		/// The part of its bond a feeder is slashed for missing
+		/// `MaxMissedVotes` price votes.
+		#[pallet::constant]
+		type MissSlashFraction: Get<Perbill>;
+
+		/// The part of each supply expansion paid to the price vote reward
+		/// pool.
+		#[pallet::constant]
+		type OracleRewardShare: Get<Perbill>;
+
+		/// The number of blocks after which a bond expires, if bonds
+		/// expire.
+		#[pallet::constant]
+		type BondLifetime: Get<Option<Self::BlockNumber>>;
+
+		/// The maximum number of bonds queued for each settcurrency.
+		#[pallet::constant]
+		type MaxBondsPerCurrency: Get<u32>;
+
+		/// The minimum amount of settcurrency burned to buy bonds.
+		#[pallet::constant]
+		type MinBondAmount: Get<BalanceOf<Self>>;
+
+		/// The maximum number of beneficiaries in the seigniorage
+		/// distribution of a currency.
+		#[pallet::constant]
+		type MaxBeneficiaries: Get<u32>;
+
+		/// The lock identifier of the native currency staked in the Market
+		/// staking pool.
+		#[pallet::constant]
+		type StakingLockId: Get<LockIdentifier>;
+
+		/// The part of each supply expansion paid to the stakers of the
+		/// native currency.
+		#[pallet::constant]
+		type StakerShare: Get<Perbill>;
+
+		/// The number of blocks a SERP supply auction runs for.
+		#[pallet::constant]
+		type AuctionDuration: Get<Self::BlockNumber>;
+
+		/// The minimum improvement of a bid on the previous bid of an
+		/// auction.
+		#[pallet::constant]
+		type AuctionMinIncrement: Get<Perbill>;
+
+		/// The number of blocks an auction is kept open for after a bid
+		/// placed close to its end.
+		#[pallet::constant]
+		type AuctionExtension: Get<Self::BlockNumber>;
+
+		/// The fee charged on minting and redeeming settcurrency at the
+		/// peg, while the settcurrency trades at its peg.
+		#[pallet::constant]
+		type MintRedeemBaseFee: Get<Perbill>;
+
+		/// The part of the deviation of a settcurrency from its peg added
+		/// to the fee charged on minting and redeeming it.
+		#[pallet::constant]
+		type MintRedeemFeeSensitivity: Get<Perbill>;
+
+		/// The maximum amount of a settcurrency minted, and redeemed, at
+		/// the peg in a block, relative to its total issuance.
+		#[pallet::constant]
+		type MaxMintRedeemPerBlock: Get<Perbill>;
+
+		/// The tax charged on swaps between settcurrencies, paid into the
+		/// stability buffer of the settcurrency swapped from.
+		#[pallet::constant]
+		type TobinTax: Get<Perbill>;
+
+		/// The maximum number of settcurrencies in a basket.
+		#[pallet::constant]
+		type MaxBasketConstituents: Get<u32>;
+
 		/// Weight information for extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -81,6 +595,126 @@ pub mod module {
 		AmountIntoBalanceFailed,
 		/// Balance is too low.
 		BalanceTooLow,
+		/// The native currency cannot be serped.
+		NotSerpable,
+		/// The currency serped with is not the native currency.
+		UnrecognisedNativeCurrency,
+		/// The peg target must be greater than zero.
+		InvalidPegTarget,
+		/// As many currencies as can be serped automatically in a block
+		/// already have a peg target or a basket.
+		TooManyPeggedCurrencies,
+		/// The PID controller gains or integral limit are negative.
+		InvalidPidGains,
+		/// The supply change exceeds the single change limit of the currency.
+		SupplyChangeTooLarge,
+		/// The supply change limit has a zero window.
+		InvalidSupplyChangeLimit,
+		/// The supply change exceeds the window change limit of the currency.
+		SupplyChangeRateExceeded,
+		/// The currency is paused.
+		CurrencyIsPaused,
+		/// Native currency transfers are paused.
+		NativeTransfersArePaused,
+		/// The currency is globally settled.
+		CurrencySettled,
+		/// The currency is not globally settled.
+		CurrencyNotSettled,
+		/// The settlement price must be greater than zero.
+		InvalidSettlementPrice,
+		/// The amount redeemed is worth nothing at the settlement price.
+		RedeemAmountTooLow,
+		/// Swaps must be between the native currency and a settcurrency.
+		InvalidSwapPair,
+		/// The currency has no market price.
+		PriceUnavailable,
+		/// The swap pool of the settcurrency is depleted.
+		SwapPoolDepleted,
+		/// The swap output is below the minimum requested.
+		SlippageExceeded,
+		/// The liquidity pool does not have enough liquidity.
+		InsufficientLiquidity,
+		/// The liquidity added or removed is zero.
+		InvalidLiquidityAmount,
+		/// The account does not have enough liquidity shares.
+		InsufficientLiquidityShares,
+		/// The order has a zero price or amount, or is already expired.
+		InvalidOrder,
+		/// The order book side is full.
+		OrderBookFull,
+		/// The order does not exist.
+		OrderNotFound,
+		/// The order is owned by another account.
+		NotOrderOwner,
+		/// No order id is available.
+		NoAvailableOrderId,
+		/// The account is not an oracle feeder.
+		NotFeeder,
+		/// The account is already an oracle feeder.
+		FeederAlreadyAdded,
+		/// The maximum number of oracle feeders is reached.
+		TooManyFeeders,
+		/// The fed price is zero.
+		InvalidFeedPrice,
+		/// The fed price deviates too far from the accepted price.
+		PriceDeviationTooLarge,
+		/// The feeder bond is too low.
+		InsufficientFeederBond,
+		/// The unbonded feeder bond cannot be withdrawn yet.
+		FeederBondUnbonding,
+		/// No price vote was committed in the previous vote period.
+		NoPriceVoteCommit,
+		/// The revealed price vote does not match its commit.
+		PriceVoteMismatch,
+		/// The currency has no peg target or basket.
+		NotPegged,
+		/// The market price of the settcurrency is not below its peg target.
+		NotBelowPeg,
+		/// The bond amount is below `MinBondAmount`, or zero.
+		InvalidBondAmount,
+		/// The bond amount exceeds the bonds offered by the SERP.
+		BondOfferExceeded,
+		/// The bond queue of the settcurrency is full.
+		BondQueueFull,
+		/// No bond id is available.
+		NoAvailableBondId,
+		/// The seigniorage distribution has too many beneficiaries or its
+		/// shares add up to more than 100%.
+		InvalidSeigniorageDistribution,
+		/// The amount staked or unstaked is zero.
+		InvalidStakeAmount,
+		/// The account has not staked enough.
+		InsufficientStake,
+		/// The amount saved or withdrawn is zero.
+		InvalidSavingsAmount,
+		/// The account has not saved enough.
+		InsufficientSavings,
+		/// The auction does not exist.
+		AuctionNotFound,
+		/// The bid does not improve enough on the best bid of the auction.
+		InvalidBid,
+		/// No auction id is available.
+		NoAvailableAuctionId,
+		/// The amount taken is zero, more than the lot left, or worth
+		/// nothing at the auction price.
+		InvalidTakeAmount,
+		/// The descending price auction fell below its floor price.
+		AuctionPriceBelowFloor,
+		/// The descending price auction curve has no floor price.
+		InvalidDutchCurve,
+		/// The amount minted or redeemed is worth nothing after the fee.
+		InvalidMintRedeemAmount,
+		/// The amount minted or redeemed in this block would exceed the
+		/// limit.
+		MintRedeemLimitExceeded,
+		/// Settcurrency swaps must be between two different settcurrencies.
+		InvalidSettSwapPair,
+		/// The basket has too many constituents, constituents that are not
+		/// settcurrencies or are baskets themselves, or weights that do
+		/// not add up to 100%.
+		InvalidBasket,
+		/// The base unit of a currency already issued would change.
+		BaseUnitChange,
 	}
 
 	#[pallet::event]
@@ -98,13 +732,609 @@ pub mod module {
 		SerpedUpSupply(CurrencyIdOf<T>, BalanceOf<T>),
 		/// Supply Contraction Successful. \[currency_id, contract_by\]
 		SerpedDownSupply(CurrencyIdOf<T>, BalanceOf<T>),
+		/// Peg target updated. \[currency_id, peg_target\]
+		PegTargetUpdated(CurrencyIdOf<T>, Option<Price>),
+		/// PID controller gains updated. \[currency_id, gains\]
+		PidGainsUpdated(CurrencyIdOf<T>, Option<PidGains>),
+		/// Peg band updated. \[currency_id, band\]
+		PegBandUpdated(CurrencyIdOf<T>, Option<Perbill>),
+		/// Supply adjustment skipped, the market price is within the peg
+		/// band. \[currency_id\]
+		SerpWithinBand(CurrencyIdOf<T>),
+		/// Automatic supply adjustment skipped, the currency is on peg or
+		/// has no market price. \[currency_id\]
+		SerpSkipped(CurrencyIdOf<T>),
+		/// Automatic supply adjustment failed. \[currency_id, error\]
+		SerpFailed(CurrencyIdOf<T>, DispatchError),
+		/// Supply change limit updated. \[currency_id, limit\]
+		SupplyChangeLimitUpdated(CurrencyIdOf<T>, Option<SupplyChangeLimit<T::BlockNumber>>),
+		/// Supply change rejected for exceeding a limit. \[currency_id, amount\]
+		SupplyChangeLimitExceeded(CurrencyIdOf<T>, BalanceOf<T>),
+		/// Currency paused. \[currency_id\]
+		CurrencyPaused(CurrencyIdOf<T>),
+		/// Currency unpaused. \[currency_id\]
+		CurrencyUnpaused(CurrencyIdOf<T>),
+		/// Native currency transfers paused or unpaused. \[paused\]
+		NativeTransfersPauseUpdated(bool),
+		/// Currency globally settled. \[currency_id, final_price\]
+		GlobalSettled(CurrencyIdOf<T>, BalanceOf<T>),
+		/// Settled currency redeemed for native currency. \[currency_id, who,
+		/// redeemed_amount, native_amount, remaining_supply\]
+		Redeemed(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>, BalanceOf<T>, BalanceOf<T>),
+		/// Currencies swapped. \[who, from_currency, to_currency, amount_in,
+		/// amount_out, fee\]
+		Swapped(T::AccountId, CurrencyIdOf<T>, CurrencyIdOf<T>, BalanceOf<T>, BalanceOf<T>, BalanceOf<T>),
+		/// Liquidity added to a pool. \[who, currency_id, native_amount,
+		/// stable_amount, shares\]
+		LiquidityAdded(T::AccountId, CurrencyIdOf<T>, BalanceOf<T>, BalanceOf<T>, BalanceOf<T>),
+		/// Liquidity removed from a pool. \[who, currency_id, native_amount,
+		/// stable_amount, shares\]
+		LiquidityRemoved(T::AccountId, CurrencyIdOf<T>, BalanceOf<T>, BalanceOf<T>, BalanceOf<T>),
+		/// Currencies swapped through a liquidity pool. \[who, currency_in,
+		/// currency_out, amount_in, amount_out\]
+		PoolSwapped(T::AccountId, CurrencyIdOf<T>, CurrencyIdOf<T>, BalanceOf<T>, BalanceOf<T>),
+		/// Limit order placed. \[order_id, who, currency_id, side, price,
+		/// amount\]
+		OrderPlaced(OrderId, T::AccountId, CurrencyIdOf<T>, OrderSide, BalanceOf<T>, BalanceOf<T>),
+		/// Limit order filled. \[maker_order_id, taker_order_id, currency_id,
+		/// amount, native_amount\]
+		OrderFilled(OrderId, OrderId, CurrencyIdOf<T>, BalanceOf<T>, BalanceOf<T>),
+		/// Limit order cancelled. \[order_id\]
+		OrderCancelled(OrderId),
+		/// Limit order expired. \[order_id\]
+		OrderExpired(OrderId),
+		/// Oracle feeder added. \[who\]
+		FeederAdded(T::AccountId),
+		/// Oracle feeder removed. \[who\]
+		FeederRemoved(T::AccountId),
+		/// Price fed to the oracle. \[who, currency_id, price\]
+		PriceFed(T::AccountId, CurrencyIdOf<T>, Price),
+		/// Feeder bond increased. \[who, amount\]
+		FeederBonded(T::AccountId, BalanceOf<T>),
+		/// Feeder bond decreased, and left unbonding. \[who, amount\]
+		FeederUnbonded(T::AccountId, BalanceOf<T>),
+		/// Unbonded feeder bond withdrawn. \[who, amount\]
+		FeederBondWithdrawn(T::AccountId, BalanceOf<T>),
+		/// Price vote committed. \[who, currency_id\]
+		PriceVoteCommitted(T::AccountId, CurrencyIdOf<T>),
+		/// Price vote revealed. \[who, currency_id, price\]
+		PriceVoteRevealed(T::AccountId, CurrencyIdOf<T>, Price),
+		/// Price votes tallied and their weighted median accepted.
+		/// \[currency_id, price\]
+		PriceVoteTallied(CurrencyIdOf<T>, Price),
+		/// Feeder rewarded for a price vote within the reward band. \[who,
+		/// currency_id, amount\]
+		FeederRewarded(T::AccountId, CurrencyIdOf<T>, BalanceOf<T>),
+		/// Feeder bond slashed for missed price votes. \[who, amount\]
+		FeederSlashed(T::AccountId, BalanceOf<T>),
+		/// Bonds bought by burning a settcurrency. \[bond_id, who,
+		/// currency_id, burned_amount, bond_amount\]
+		BondsBought(BondId, T::AccountId, CurrencyIdOf<T>, BalanceOf<T>, BalanceOf<T>),
+		/// Bonds offered to contract the supply of a settcurrency.
+		/// \[currency_id, amount\]
+		BondsOffered(CurrencyIdOf<T>, BalanceOf<T>),
+		/// Bond redeemed out of a supply expansion. \[bond_id, who,
+		/// currency_id, amount\]
+		BondRedeemed(BondId, T::AccountId, CurrencyIdOf<T>, BalanceOf<T>),
+		/// Bond expired before it was fully redeemed. \[bond_id\]
+		BondExpired(BondId),
+		/// Seigniorage distribution updated. \[currency_id, distribution\]
+		SeigniorageDistributionUpdated(CurrencyIdOf<T>, Vec<(T::AccountId, Perbill)>),
+		/// Seigniorage paid out to the beneficiaries of the distribution.
+		/// \[currency_id, payouts\]
+		SeigniorageDistributed(CurrencyIdOf<T>, Vec<(T::AccountId, BalanceOf<T>)>),
+		/// Native currency staked. \[who, amount\]
+		Staked(T::AccountId, BalanceOf<T>),
+		/// Native currency unstaked. \[who, amount\]
+		Unstaked(T::AccountId, BalanceOf<T>),
+		/// Seigniorage paid to the stakers. \[currency_id, amount\]
+		StakersRewarded(CurrencyIdOf<T>, BalanceOf<T>),
+		/// Staking rewards claimed. \[who, currency_id, amount\]
+		StakingRewardsClaimed(T::AccountId, CurrencyIdOf<T>, BalanceOf<T>),
+		/// Savings rate updated. \[currency_id, rate\]
+		SavingsRateUpdated(CurrencyIdOf<T>, Option<Price>),
+		/// Settcurrency deposited into savings. \[who, currency_id, amount\]
+		SavingsDeposited(T::AccountId, CurrencyIdOf<T>, BalanceOf<T>),
+		/// Settcurrency withdrawn from savings. \[who, currency_id, amount\]
+		SavingsWithdrawn(T::AccountId, CurrencyIdOf<T>, BalanceOf<T>),
+		/// Stability buffer shares updated. \[currency_id, shares\]
+		StabilityBufferSharesUpdated(CurrencyIdOf<T>, Option<BufferShares>),
+		/// Paid into the stability buffer. \[currency_id, amount\]
+		StabilityBufferFunded(CurrencyIdOf<T>, BalanceOf<T>),
+		/// Burned from the stability buffer to contract the supply.
+		/// \[currency_id, amount\]
+		StabilityBufferBurned(CurrencyIdOf<T>, BalanceOf<T>),
+		/// SERP mode updated. \[currency_id, mode\]
+		SerpModeUpdated(CurrencyIdOf<T>, SerpMode),
+		/// Auction started. \[auction_id, currency_id, kind, lot\]
+		AuctionStarted(AuctionId, CurrencyIdOf<T>, AuctionKind, BalanceOf<T>),
+		/// Bid placed on an auction. \[auction_id, who, bid\]
+		AuctionBid(AuctionId, T::AccountId, BalanceOf<T>),
+		/// Auction settled to the best bid. \[auction_id, winner, bid\]
+		AuctionSettled(AuctionId, T::AccountId, BalanceOf<T>),
+		/// Auction ended without being settled. \[auction_id\]
+		AuctionAborted(AuctionId),
+		/// Descending price auction started. \[auction_id, currency_id, kind,
+		/// lot, start_price\]
+		DutchAuctionStarted(AuctionId, CurrencyIdOf<T>, AuctionKind, BalanceOf<T>, Price),
+		/// Slice of a descending price auction taken. \[auction_id, who,
+		/// amount, native_amount\]
+		DutchAuctionTaken(AuctionId, T::AccountId, BalanceOf<T>, BalanceOf<T>),
+		/// Descending price auction ended. \[auction_id, unsold\]
+		DutchAuctionEnded(AuctionId, BalanceOf<T>),
+		/// Settcurrency minted at the peg for native currency. \[who,
+		/// currency_id, native_amount, minted, fee\]
+		SettMinted(T::AccountId, CurrencyIdOf<T>, BalanceOf<T>, BalanceOf<T>, BalanceOf<T>),
+		/// Settcurrency redeemed at the peg for native currency. \[who,
+		/// currency_id, redeemed, native_amount, fee\]
+		SettRedeemed(T::AccountId, CurrencyIdOf<T>, BalanceOf<T>, BalanceOf<T>, BalanceOf<T>),
+		/// Basket updated. \[currency_id, constituents\]
+		BasketUpdated(CurrencyIdOf<T>, Vec<(CurrencyIdOf<T>, Perbill)>),
+		/// Settcurrency swapped for another settcurrency. \[who,
+		/// from_currency, to_currency, amount_in, amount_out, tax, rate\]
+		SettSwapped(
+			T::AccountId,
+			CurrencyIdOf<T>,
+			CurrencyIdOf<T>,
+			BalanceOf<T>,
+			BalanceOf<T>,
+			BalanceOf<T>,
+			Price,
+		),
 	}
 
+	/// The most recent supply adjustments of each currency, oldest first.
+	///
+	/// SupplyAdjustments: map CurrencyId => Vec<SupplyAdjustment>
+	#[pallet::storage]
+	#[pallet::getter(fn supply_adjustments)]
+	pub type SupplyAdjustments<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		CurrencyIdOf<T>,
+		Vec<SupplyAdjustment<T::BlockNumber, BalanceOf<T>>>,
+		ValueQuery,
+	>;
+
+	/// The price each currency is serped towards, in the unit of account of
+	/// the `Oracle`. Only currencies with a peg target or a basket are
+	/// serped automatically.
+	///
+	/// PegTargets: map CurrencyId => Option<Price>
+	#[pallet::storage]
+	#[pallet::getter(fn peg_target)]
+	pub type PegTargets<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, Price, OptionQuery>;
+
+	/// The PID controller gains of each currency. Currencies with gains are
+	/// serped by their PID controller, others in proportion to their price
+	/// error.
+	///
+	/// PidControllers: map CurrencyId => Option<PidGains>
+	#[pallet::storage]
+	#[pallet::getter(fn pid_gains)]
+	pub type PidControllers<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, PidGains, OptionQuery>;
+
+	/// The weighted settcurrencies each basket currency is composed of. The
+	/// peg of a basket follows the market prices of its constituents.
+	///
+	/// Baskets: map CurrencyId => Vec<(CurrencyId, Perbill)>
+	#[pallet::storage]
+	#[pallet::getter(fn basket)]
+	pub type Baskets<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyIdOf<T>, Vec<(CurrencyIdOf<T>, Perbill)>, ValueQuery>;
+
+	/// The number of baskets each settcurrency is a constituent of.
+	///
+	/// BasketConstituentCounts: map CurrencyId => u32
+	#[pallet::storage]
+	#[pallet::getter(fn basket_constituent_count)]
+	pub type BasketConstituentCounts<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, u32, ValueQuery>;
+
+	/// The band around the peg target of each currency, relative to it,
+	/// within which the supply is not adjusted.
+	///
+	/// PegBands: map CurrencyId => Option<Perbill>
+	#[pallet::storage]
+	#[pallet::getter(fn peg_band)]
+	pub type PegBands<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, Perbill, OptionQuery>;
+
+	/// The shares paid into the stability buffer of each currency.
+	///
+	/// StabilityBufferShares: map CurrencyId => Option<BufferShares>
+	#[pallet::storage]
+	#[pallet::getter(fn stability_buffer_shares)]
+	pub type StabilityBufferShares<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, BufferShares, OptionQuery>;
+
+	/// How the SERP changes the supply of each currency.
+	///
+	/// SerpModes: map CurrencyId => SerpMode
+	#[pallet::storage]
+	#[pallet::getter(fn serp_mode)]
+	pub type SerpModes<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, SerpMode, ValueQuery>;
+
+	/// The PID controller state of each currency, after its last
+	/// successful supply adjustment.
+	///
+	/// PidStates: map CurrencyId => Option<PidState>
+	#[pallet::storage]
+	#[pallet::getter(fn pid_state)]
+	pub type PidStates<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, PidState, OptionQuery>;
+
+	/// The supply change limits of each currency.
+	///
+	/// SupplyChangeLimits: map CurrencyId => Option<SupplyChangeLimit>
+	#[pallet::storage]
+	#[pallet::getter(fn supply_change_limit)]
+	pub type SupplyChangeLimits<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyIdOf<T>, SupplyChangeLimit<T::BlockNumber>, OptionQuery>;
+
+	/// The supply changes of each limited currency within its trailing
+	/// window, as `(block, changed)` per block, oldest first.
+	///
+	/// SupplyChangeWindows: map CurrencyId => Vec<(BlockNumber, Balance)>
+	#[pallet::storage]
+	#[pallet::getter(fn supply_change_window)]
+	pub type SupplyChangeWindows<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyIdOf<T>, Vec<(T::BlockNumber, BalanceOf<T>)>, ValueQuery>;
+
+	/// The currencies that are paused.
+	///
+	/// PausedCurrencies: map CurrencyId => Option<()>
+	#[pallet::storage]
+	pub type PausedCurrencies<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, (), OptionQuery>;
+
+	/// Whether native currency transfers are paused.
+	///
+	/// NativeTransfersPaused: bool
+	#[pallet::storage]
+	#[pallet::getter(fn native_transfers_paused)]
+	pub type NativeTransfersPaused<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// The final price of each globally settled currency, in native
+	/// currency per `base_unit` of the settled currency.
+	///
+	/// SettlementPrices: map CurrencyId => Option<Balance>
+	#[pallet::storage]
+	#[pallet::getter(fn settlement_price)]
+	pub type SettlementPrices<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, BalanceOf<T>, OptionQuery>;
+
+	/// The native currency in the settlement reserve set aside for
+	/// redeeming each globally settled currency.
+	///
+	/// SettlementReserves: map CurrencyId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn settlement_reserve)]
+	pub type SettlementReserves<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, BalanceOf<T>, ValueQuery>;
+
+	/// The delta of the virtual swap pool of each settcurrency from its
+	/// base liquidity, in units of the settcurrency.
+	///
+	/// SwapPoolDeltas: map CurrencyId => i128
+	#[pallet::storage]
+	#[pallet::getter(fn swap_pool_delta)]
+	pub type SwapPoolDeltas<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, i128, ValueQuery>;
+
+	/// The reserves of the liquidity pool of each settcurrency, as
+	/// `(native_reserve, stable_reserve)`.
+	///
+	/// LiquidityPools: map CurrencyId => (Balance, Balance)
+	#[pallet::storage]
+	#[pallet::getter(fn liquidity_pool)]
+	pub type LiquidityPools<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyIdOf<T>, (BalanceOf<T>, BalanceOf<T>), ValueQuery>;
+
+	/// The liquidity shares of each account in each liquidity pool.
+	///
+	/// LiquidityShares: double_map CurrencyId, AccountId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn liquidity_shares)]
+	pub type LiquidityShares<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, CurrencyIdOf<T>, Twox64Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	/// The total liquidity shares of each liquidity pool.
+	///
+	/// TotalLiquidityShares: map CurrencyId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn total_liquidity_shares)]
+	pub type TotalLiquidityShares<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, BalanceOf<T>, ValueQuery>;
+
+	/// The id of the next limit order.
+	///
+	/// NextOrderId: OrderId
+	#[pallet::storage]
+	#[pallet::getter(fn next_order_id)]
+	pub type NextOrderId<T: Config> = StorageValue<_, OrderId, ValueQuery>;
+
+	/// The resting limit orders.
+	///
+	/// Orders: map OrderId => Option<Order>
+	#[pallet::storage]
+	#[pallet::getter(fn orders)]
+	pub type Orders<T: Config> = StorageMap<_, Twox64Concat, OrderId, OrderOf<T>, OptionQuery>;
+
+	/// The resting orders on each side of the order book of each
+	/// settcurrency, as `(price, order_id)` in matching priority.
+	///
+	/// OrderBooks: double_map CurrencyId, OrderSide => Vec<(Balance, OrderId)>
+	#[pallet::storage]
+	#[pallet::getter(fn order_book)]
+	pub type OrderBooks<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		CurrencyIdOf<T>,
+		Twox64Concat,
+		OrderSide,
+		Vec<(BalanceOf<T>, OrderId)>,
+		ValueQuery,
+	>;
+
+	/// The resting orders expiring at each block.
+	///
+	/// OrderExpiries: double_map BlockNumber, OrderId => Option<()>
+	#[pallet::storage]
+	pub type OrderExpiries<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::BlockNumber, Twox64Concat, OrderId, (), OptionQuery>;
+
+	/// The recent trade price observations of each currency pair, oldest
+	/// first, with the price of the first currency in the second.
+	///
+	/// PriceObservations: double_map CurrencyId, CurrencyId => Vec<PriceObservation>
+	#[pallet::storage]
+	#[pallet::getter(fn price_observations)]
+	pub type PriceObservations<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		CurrencyIdOf<T>,
+		Twox64Concat,
+		CurrencyIdOf<T>,
+		Vec<PriceObservation<T::BlockNumber, BalanceOf<T>>>,
+		ValueQuery,
+	>;
+
+	/// The accounts allowed to feed prices to the oracle.
+	///
+	/// Feeders: Vec<AccountId>
+	#[pallet::storage]
+	#[pallet::getter(fn feeders)]
+	pub type Feeders<T: Config> = StorageValue<_, Vec<T::AccountId>, ValueQuery>;
+
+	/// The latest price fed by each feeder for each currency, with the
+	/// block it was fed in.
+	///
+	/// RawPrices: double_map CurrencyId, AccountId => Option<(Price, BlockNumber)>
+	#[pallet::storage]
+	#[pallet::getter(fn raw_price)]
+	pub type RawPrices<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		CurrencyIdOf<T>,
+		Twox64Concat,
+		T::AccountId,
+		(Price, T::BlockNumber),
+		OptionQuery,
+	>;
+
+	/// The median of the fresh fed prices of each currency, with the block
+	/// it was accepted in.
+	///
+	/// AcceptedPrices: map CurrencyId => Option<(Price, BlockNumber)>
+	#[pallet::storage]
+	#[pallet::getter(fn accepted_price)]
+	pub type AcceptedPrices<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyIdOf<T>, (Price, T::BlockNumber), OptionQuery>;
+
+	/// The native currency bonded by each feeder, reserved from its
+	/// account and weighting its price votes.
+	///
+	/// FeederBonds: map AccountId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn feeder_bond)]
+	pub type FeederBonds<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	/// The native currency unbonded by each feeder, still reserved and
+	/// slashable, with the block it can be withdrawn at.
+	///
+	/// FeederUnbonding: map AccountId => Option<(Balance, BlockNumber)>
+	#[pallet::storage]
+	#[pallet::getter(fn feeder_unbonding)]
+	pub type FeederUnbonding<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, (BalanceOf<T>, T::BlockNumber), OptionQuery>;
+
+	/// The weighted median of the price votes of each currency, with the
+	/// block it was tallied in. Fed prices do not override it.
+	///
+	/// VotedPrices: map CurrencyId => Option<(Price, BlockNumber)>
+	#[pallet::storage]
+	#[pallet::getter(fn voted_price)]
+	pub type VotedPrices<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyIdOf<T>, (Price, T::BlockNumber), OptionQuery>;
+
+	/// The price vote commit of each feeder for each currency, with the
+	/// vote period it was committed in.
+	///
+	/// PriceVoteCommits: double_map CurrencyId, AccountId => Option<(BlockNumber, Hash)>
+	#[pallet::storage]
+	#[pallet::getter(fn price_vote_commit)]
+	pub type PriceVoteCommits<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		CurrencyIdOf<T>,
+		Twox64Concat,
+		T::AccountId,
+		(T::BlockNumber, T::Hash),
+		OptionQuery,
+	>;
+
+	/// The price votes revealed in the current vote period.
+	///
+	/// PriceVotes: double_map CurrencyId, AccountId => Option<Price>
+	#[pallet::storage]
+	#[pallet::getter(fn price_vote)]
+	pub type PriceVotes<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, CurrencyIdOf<T>, Twox64Concat, T::AccountId, Price, OptionQuery>;
+
+	/// The number of price votes each feeder missed since it was last
+	/// slashed.
+	///
+	/// MissedVotes: map AccountId => u32
+	#[pallet::storage]
+	#[pallet::getter(fn missed_votes)]
+	pub type MissedVotes<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
+	/// The id of the next bond.
+	///
+	/// NextBondId: BondId
+	#[pallet::storage]
+	#[pallet::getter(fn next_bond_id)]
+	pub type NextBondId<T: Config> = StorageValue<_, BondId, ValueQuery>;
+
+	/// The bonds not yet redeemed.
+	///
+	/// Bonds: map BondId => Option<Bond>
+	#[pallet::storage]
+	#[pallet::getter(fn bonds)]
+	pub type Bonds<T: Config> = StorageMap<_, Twox64Concat, BondId, BondOf<T>, OptionQuery>;
+
+	/// The share of the seigniorage of each currency paid to each of its
+	/// beneficiaries. The seigniorage left over is serped as before.
+	///
+	/// SeigniorageDistributions: map CurrencyId => Vec<(AccountId, Perbill)>
+	#[pallet::storage]
+	#[pallet::getter(fn seigniorage_distribution)]
+	pub type SeigniorageDistributions<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyIdOf<T>, Vec<(T::AccountId, Perbill)>, ValueQuery>;
+
+	/// The native currency staked by each account.
+	///
+	/// Stakes: map AccountId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn stakes)]
+	pub type Stakes<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	/// The total native currency staked.
+	///
+	/// TotalStaked: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn total_staked)]
+	pub type TotalStaked<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	/// The staking rewards paid in each currency per unit of native
+	/// currency staked, accumulated since genesis.
+	///
+	/// StakingRewardPerShare: map CurrencyId => Price
+	#[pallet::storage]
+	#[pallet::getter(fn staking_reward_per_share)]
+	pub type StakingRewardPerShare<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, Price, ValueQuery>;
+
+	/// The staking rewards in each currency already accounted for each
+	/// staker.
+	///
+	/// StakingRewardDebts: double_map AccountId, CurrencyId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn staking_reward_debt)]
+	pub type StakingRewardDebts<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, CurrencyIdOf<T>, BalanceOf<T>, ValueQuery>;
+
+	/// The savings rate of each settcurrency, per block.
+	///
+	/// SavingsRates: map CurrencyId => Option<Price>
+	#[pallet::storage]
+	#[pallet::getter(fn savings_rate)]
+	pub type SavingsRates<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, Price, OptionQuery>;
+
+	/// The value of one unit of savings of each settcurrency, with the
+	/// block interest was last accrued in.
+	///
+	/// SavingsChi: map CurrencyId => Option<(Price, BlockNumber)>
+	#[pallet::storage]
+	#[pallet::getter(fn savings_chi)]
+	pub type SavingsChi<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyIdOf<T>, (Price, T::BlockNumber), OptionQuery>;
+
+	/// The units of savings of each settcurrency held by each account.
+	///
+	/// SavingsBalances: double_map CurrencyId, AccountId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn savings_balance)]
+	pub type SavingsBalances<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, CurrencyIdOf<T>, Twox64Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	/// The total units of savings of each settcurrency.
+	///
+	/// TotalSavings: map CurrencyId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn total_savings)]
+	pub type TotalSavings<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, BalanceOf<T>, ValueQuery>;
+
+	/// The id of the next SERP supply auction.
+	///
+	/// NextAuctionId: AuctionId
+	#[pallet::storage]
+	#[pallet::getter(fn next_auction_id)]
+	pub type NextAuctionId<T: Config> = StorageValue<_, AuctionId, ValueQuery>;
+
+	/// The running SERP supply auctions.
+	///
+	/// Auctions: map AuctionId => Option<Auction>
+	#[pallet::storage]
+	#[pallet::getter(fn auctions)]
+	pub type Auctions<T: Config> = StorageMap<_, Twox64Concat, AuctionId, AuctionOf<T>, OptionQuery>;
+
+	/// The running descending price SERP supply auctions.
+	///
+	/// DutchAuctions: map AuctionId => Option<DutchAuction>
+	#[pallet::storage]
+	#[pallet::getter(fn dutch_auctions)]
+	pub type DutchAuctions<T: Config> = StorageMap<_, Twox64Concat, AuctionId, DutchAuctionOf<T>, OptionQuery>;
+
+	/// The auctions ending at each block.
+	///
+	/// AuctionEndTimes: double_map BlockNumber, AuctionId => Option<()>
+	#[pallet::storage]
+	pub type AuctionEndTimes<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::BlockNumber, Twox64Concat, AuctionId, (), OptionQuery>;
+
+	/// The amounts of each settcurrency minted and redeemed at the peg in
+	/// the block they were last minted or redeemed in.
+	///
+	/// MintRedeemVolumes: map CurrencyId => (BlockNumber, Balance, Balance)
+	#[pallet::storage]
+	#[pallet::getter(fn mint_redeem_volume)]
+	pub type MintRedeemVolumes<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyIdOf<T>, (T::BlockNumber, BalanceOf<T>, BalanceOf<T>), ValueQuery>;
+
+	/// The bonds of each settcurrency in redemption order.
+	///
+	/// BondQueues: map CurrencyId => Vec<BondId>
+	#[pallet::storage]
+	#[pallet::getter(fn bond_queue)]
+	pub type BondQueues<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, Vec<BondId>, ValueQuery>;
+
+	/// The amount of each settcurrency the SERP offers to burn for bonds,
+	/// in the `Bonds` SERP mode.
+	///
+	/// BondOffers: map CurrencyId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn bond_offer)]
+	pub type BondOffers<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, BalanceOf<T>, ValueQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(PhantomData<T>);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		/// Expire limit orders and recover the swap pools towards balance
+		/// every block, tally the price votes at the end of every vote
+		/// period, and serp every pegged currency towards its peg target
+		/// once every `AdjustmentPeriod` blocks.
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			Self::expire_orders(now)
+				.saturating_add(Self::recover_swap_pools())
+				.saturating_add(Self::tally_price_votes(now))
+				.saturating_add(Self::settle_auctions(now))
+				.saturating_add(Self::serp_pegged_currencies(now))
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -137,6 +1367,7 @@ pub mod module {
 		) -> DispatchResultWithPostInfo {
 			let from = ensure_signed(origin)?;
 			let to = T::Lookup::lookup(dest)?;
+			ensure!(!Self::native_transfers_paused(), Error::<T>::NativeTransfersArePaused);
 			T::Stp258Native::transfer(&from, &to, amount)?;
 
 			Self::deposit_event(Event::Transferred(T::GetStp258NativeId::get(), from, to, amount));
@@ -158,78 +1389,3119 @@ pub mod module {
 			<Self as Stp258CurrencyExtended<T::AccountId>>::update_balance(currency_id, &dest, amount)?;
 			Ok(().into())
 		}
-	}
-}
 
-impl<T: Config> SerpMarket<T::AccountId> for Pallet<T> {
-	/// Called when `expand_supply` is received from the SERP by the SerpTes 
-	/// through the `on_expand_supply` trigger.
-	/// Implementation should `deposit` the `amount` to `serpup_to`, 
-	/// then `amount` will be slashed from `serpup_from` and update
-	/// `new_supply`. `quote_price` is the price ( relative to the settcurrency) of 
-	/// the `native_currency` used to expand settcurrency supply.
-	/// `who` is the account to serp with.
-	/// `quote_price` here is sampled from mock and can be connected to an oracle.
-	fn expand_supply(
-		native_currency_id: Self::CurrencyId, 
-		stable_currency_id: Self::CurrencyId, 
-		expand_by: Self::Balance, 
-		quote_price: Self::Balance, 
-	) -> DispatchResult {
-		if expand_by.is_zero() {
-			return Ok(());
+		/// Expand the supply of `currency_id` by `expand_by` through the SERP,
+		/// serping with the native currency at the oracle quote price. As an
+		/// emergency adjustment, it serps even within the peg band.
+		///
+		/// The dispatch origin of this call must be `SerpOrigin`.
+		#[pallet::weight(T::WeightInfo::expand_supply())]
+		pub fn expand_supply(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] expand_by: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			T::SerpOrigin::ensure_origin(origin)?;
+			Self::do_expand_supply(T::GetStp258NativeId::get(), currency_id, expand_by)?;
+			Ok(().into())
 		}
-		if native_currency_id == T::GetStp258NativeId::get() {
-			if stable_currency_id != T::GetStp258NativeId::get() {
-				T::Stp258Currency::expand_supply(
-					native_currency_id, 
-					stable_currency_id, 
-					expand_by as Self::Balance, 
-					quote_price,
-				)?;
-			} else {
-				native::info!("💸 This currency cannot be serped.");
+
+		/// Contract the supply of `currency_id` by `contract_by` through the SERP,
+		/// serping with the native currency at the oracle quote price. As an
+		/// emergency adjustment, it serps even within the peg band.
+		///
+		/// The dispatch origin of this call must be `SerpOrigin`.
+		#[pallet::weight(T::WeightInfo::contract_supply())]
+		pub fn contract_supply(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] contract_by: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			T::SerpOrigin::ensure_origin(origin)?;
+			Self::do_contract_supply(T::GetStp258NativeId::get(), currency_id, contract_by)?;
+			Ok(().into())
+		}
+
+		/// Set or clear the peg target of `currency_id`.
+		///
+		/// The dispatch origin of this call must be `SerpOrigin`.
+		#[pallet::weight(T::WeightInfo::set_peg_target())]
+		pub fn set_peg_target(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			peg_target: Option<Price>,
+		) -> DispatchResultWithPostInfo {
+			T::SerpOrigin::ensure_origin(origin)?;
+			Self::ensure_serpable(T::GetStp258NativeId::get(), currency_id)?;
+			ensure!(
+				peg_target.map_or(true, |target| !target.is_zero()),
+				Error::<T>::InvalidPegTarget
+			);
+			if peg_target.is_some() {
+				Self::ensure_can_auto_serp(currency_id)?;
 			}
-		} else {
-			native::info!("💸 The native serping currency is not recognised.");
+			PegTargets::<T>::mutate(currency_id, |target| *target = peg_target);
+			Self::deposit_event(Event::PegTargetUpdated(currency_id, peg_target));
+			Ok(().into())
 		}
-		Self::deposit_event(Event::SerpedUpSupply(stable_currency_id, expand_by));
-		Ok(())
-	}
 
-	/// Called when `contract_supply` is received from the SERP by the SerpTes 
-	/// through the `on_contract_supply` trigger.
-	/// Implementation should `deposit` the `base_currency_id` (The Native Currency) 
-	/// of `amount` to `serpup_to`, then `amount` will be slashed from `serpup_from` 
-	/// and update `new_supply`. `quote_price` is the price ( relative to the settcurrency) of 
-	/// the `native_currency` used to contract settcurrency supply.
-	/// `who` is the account to serp with.
-	/// `quote_price` here is sampled from mock and can be connected to an oracle.
-	fn contract_supply(
-		native_currency_id: Self::CurrencyId, 
-		stable_currency_id: Self::CurrencyId, 
-		contract_by: Self::Balance, 
-		quote_price: Self::Balance, 
-	) -> DispatchResult {
-		if contract_by.is_zero() {
-			return Ok(());
+		/// Set `currency_id` to be a basket of the weighted settcurrencies
+		/// `constituents`, clearing the basket if `constituents` is empty.
+		/// The base unit of a basket is derived from its definition, as the
+		/// largest base unit of its constituents, through the
+		/// `BasketBaseUnits` of the currency layer, so it can only change
+		/// while `currency_id` is not issued.
+		///
+		/// The dispatch origin of this call must be `SerpOrigin`.
+		#[pallet::weight(T::WeightInfo::set_basket(
+			(constituents.len() as u32).max(T::MaxBasketConstituents::get()),
+			T::MaxAutoSerpsPerBlock::get(),
+		))]
+		pub fn set_basket(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			constituents: Vec<(CurrencyIdOf<T>, Perbill)>,
+		) -> DispatchResultWithPostInfo {
+			T::SerpOrigin::ensure_origin(origin)?;
+			Self::ensure_serpable(T::GetStp258NativeId::get(), currency_id)?;
+			Self::ensure_not_settled(currency_id)?;
+			Self::ensure_valid_basket(currency_id, &constituents)?;
+			if !constituents.is_empty() {
+				Self::ensure_can_auto_serp(currency_id)?;
+			}
+
+			let base_unit = Self::base_unit(currency_id);
+			with_transaction_result(|| {
+				for (constituent, _) in Self::basket(currency_id) {
+					BasketConstituentCounts::<T>::mutate_exists(constituent, |count| {
+						*count = count.map(|count| count.saturating_sub(1)).filter(|count| !count.is_zero());
+					});
+				}
+				for (constituent, _) in constituents.iter() {
+					BasketConstituentCounts::<T>::mutate(constituent, |count| *count = count.saturating_add(1));
+				}
+				if constituents.is_empty() {
+					Baskets::<T>::remove(currency_id);
+				} else {
+					Baskets::<T>::insert(currency_id, &constituents);
+				}
+				ensure!(
+					Self::total_issuance(currency_id).is_zero() || Self::base_unit(currency_id) == base_unit,
+					Error::<T>::BaseUnitChange
+				);
+				Ok(())
+			})?;
+			Self::deposit_event(Event::BasketUpdated(currency_id, constituents));
+			Ok(().into())
+		}
+
+		/// Set or clear the band around the peg target of `currency_id`
+		/// within which its supply is not adjusted.
+		///
+		/// The dispatch origin of this call must be `SerpOrigin`.
+		#[pallet::weight(T::WeightInfo::set_peg_band())]
+		pub fn set_peg_band(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			band: Option<Perbill>,
+		) -> DispatchResultWithPostInfo {
+			T::SerpOrigin::ensure_origin(origin)?;
+			Self::ensure_serpable(T::GetStp258NativeId::get(), currency_id)?;
+			PegBands::<T>::mutate(currency_id, |peg_band| *peg_band = band);
+			Self::deposit_event(Event::PegBandUpdated(currency_id, band));
+			Ok(().into())
 		}
-		if native_currency_id == T::GetStp258NativeId::get() {
-			if stable_currency_id != T::GetStp258NativeId::get() {
-				T::Stp258Currency::contract_supply(
-					native_currency_id, 
-					stable_currency_id, 
-					contract_by,
-					quote_price,
-				)?;
+
+		/// Set the seigniorage distribution of `currency_id`, clearing it if
+		/// `distribution` is empty.
+		///
+		/// The dispatch origin of this call must be `SerpOrigin`.
+		#[pallet::weight(T::WeightInfo::set_seigniorage_distribution(distribution.len() as u32))]
+		pub fn set_seigniorage_distribution(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			distribution: Vec<(T::AccountId, Perbill)>,
+		) -> DispatchResultWithPostInfo {
+			T::SerpOrigin::ensure_origin(origin)?;
+			Self::ensure_serpable(T::GetStp258NativeId::get(), currency_id)?;
+			let total_share = distribution
+				.iter()
+				.map(|(_, share)| u64::from(share.deconstruct()))
+				.sum::<u64>();
+			ensure!(
+				distribution.len() <= T::MaxBeneficiaries::get() as usize
+					&& total_share <= u64::from(Perbill::ACCURACY),
+				Error::<T>::InvalidSeigniorageDistribution
+			);
+			if distribution.is_empty() {
+				SeigniorageDistributions::<T>::remove(currency_id);
 			} else {
-				native::info!("💸 This currency cannot be serped.");
+				SeigniorageDistributions::<T>::insert(currency_id, &distribution);
 			}
-		} else {
-			native::info!("💸 The native serping currency is not recognised.");
+			Self::deposit_event(Event::SeigniorageDistributionUpdated(currency_id, distribution));
+			Ok(().into())
 		}
-		Self::deposit_event(Event::SerpedDownSupply(stable_currency_id, contract_by));
-		Ok(())
+
+		/// Set or clear the shares of the seigniorage and swap fees of
+		/// `currency_id` paid into its stability buffer.
+		///
+		/// The dispatch origin of this call must be `SerpOrigin`.
+		#[pallet::weight(T::WeightInfo::set_stability_buffer_shares())]
+		pub fn set_stability_buffer_shares(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			shares: Option<BufferShares>,
+		) -> DispatchResultWithPostInfo {
+			T::SerpOrigin::ensure_origin(origin)?;
+			Self::ensure_serpable(T::GetStp258NativeId::get(), currency_id)?;
+			StabilityBufferShares::<T>::mutate(currency_id, |buffer_shares| *buffer_shares = shares);
+			Self::deposit_event(Event::StabilityBufferSharesUpdated(currency_id, shares));
+			Ok(().into())
+		}
+
+		/// Set how the SERP changes the supply of `currency_id`.
+		///
+		/// The dispatch origin of this call must be `SerpOrigin`.
+		#[pallet::weight(T::WeightInfo::set_serp_mode())]
+		pub fn set_serp_mode(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			mode: SerpMode,
+		) -> DispatchResultWithPostInfo {
+			T::SerpOrigin::ensure_origin(origin)?;
+			Self::ensure_serpable(T::GetStp258NativeId::get(), currency_id)?;
+			if let SerpMode::Dutch(curve) = mode {
+				ensure!(!curve.min_price.is_zero(), Error::<T>::InvalidDutchCurve);
+			}
+			if mode != SerpMode::Bonds {
+				BondOffers::<T>::remove(currency_id);
+			}
+			SerpModes::<T>::insert(currency_id, mode);
+			Self::deposit_event(Event::SerpModeUpdated(currency_id, mode));
+			Ok(().into())
+		}
+
+		/// Set or clear the PID controller gains of `currency_id`, resetting
+		/// the state of its controller.
+		///
+		/// The dispatch origin of this call must be `SerpOrigin`.
+		#[pallet::weight(T::WeightInfo::set_pid_gains())]
+		pub fn set_pid_gains(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			gains: Option<PidGains>,
+		) -> DispatchResultWithPostInfo {
+			T::SerpOrigin::ensure_origin(origin)?;
+			Self::ensure_serpable(T::GetStp258NativeId::get(), currency_id)?;
+			ensure!(
+				gains.map_or(true, |gains| {
+					!gains.proportional.is_negative()
+						&& !gains.integral.is_negative()
+						&& !gains.derivative.is_negative()
+						&& !gains.integral_limit.is_negative()
+				}),
+				Error::<T>::InvalidPidGains
+			);
+			PidControllers::<T>::mutate(currency_id, |pid_gains| *pid_gains = gains);
+			PidStates::<T>::remove(currency_id);
+			Self::deposit_event(Event::PidGainsUpdated(currency_id, gains));
+			Ok(().into())
+		}
+
+		/// Set or clear the supply change limit of `currency_id`.
+		///
+		/// The dispatch origin of this call must be `SerpOrigin`.
+		#[pallet::weight(T::WeightInfo::set_supply_change_limit())]
+		pub fn set_supply_change_limit(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			limit: Option<SupplyChangeLimit<T::BlockNumber>>,
+		) -> DispatchResultWithPostInfo {
+			T::SerpOrigin::ensure_origin(origin)?;
+			if let Some(limit) = limit {
+				ensure!(!limit.window.is_zero(), Error::<T>::InvalidSupplyChangeLimit);
+			}
+			SupplyChangeLimits::<T>::mutate(currency_id, |maybe_limit| *maybe_limit = limit);
+			if limit.is_none() {
+				SupplyChangeWindows::<T>::remove(currency_id);
+			}
+			Self::deposit_event(Event::SupplyChangeLimitUpdated(currency_id, limit));
+			Ok(().into())
+		}
+
+		/// Pause `currency_id`, blocking its transfers, balance updates,
+		/// deposits, withdrawals and serping.
+		///
+		/// The dispatch origin of this call must be `PauseOrigin`.
+		#[pallet::weight(T::WeightInfo::pause_currency())]
+		pub fn pause_currency(origin: OriginFor<T>, currency_id: CurrencyIdOf<T>) -> DispatchResultWithPostInfo {
+			T::PauseOrigin::ensure_origin(origin)?;
+			PausedCurrencies::<T>::insert(currency_id, ());
+			Self::deposit_event(Event::CurrencyPaused(currency_id));
+			Ok(().into())
+		}
+
+		/// Unpause `currency_id`.
+		///
+		/// The dispatch origin of this call must be `PauseOrigin`.
+		#[pallet::weight(T::WeightInfo::unpause_currency())]
+		pub fn unpause_currency(origin: OriginFor<T>, currency_id: CurrencyIdOf<T>) -> DispatchResultWithPostInfo {
+			T::PauseOrigin::ensure_origin(origin)?;
+			PausedCurrencies::<T>::remove(currency_id);
+			Self::deposit_event(Event::CurrencyUnpaused(currency_id));
+			Ok(().into())
+		}
+
+		/// Pause or unpause native currency transfers.
+		///
+		/// The dispatch origin of this call must be `PauseOrigin`.
+		#[pallet::weight(T::WeightInfo::set_native_transfers_paused())]
+		pub fn set_native_transfers_paused(origin: OriginFor<T>, paused: bool) -> DispatchResultWithPostInfo {
+			T::PauseOrigin::ensure_origin(origin)?;
+			NativeTransfersPaused::<T>::put(paused);
+			Self::deposit_event(Event::NativeTransfersPauseUpdated(paused));
+			Ok(().into())
+		}
+
+		/// Globally settle `currency_id` at `final_price`, the native
+		/// currency paid per `base_unit` of it. Serping, transfers and
+		/// deposits of the currency are frozen for good. The native currency
+		/// its supply is worth at `final_price` is set aside in the
+		/// settlement reserve, as far as the reserve holds it, and holders
+		/// may `redeem` the currency pro rata out of it.
+		///
+		/// The dispatch origin of this call must be `SerpOrigin`.
+		#[pallet::weight(T::WeightInfo::global_settle())]
+		pub fn global_settle(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] final_price: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			T::SerpOrigin::ensure_origin(origin)?;
+			Self::ensure_serpable(T::GetStp258NativeId::get(), currency_id)?;
+			Self::ensure_not_settled(currency_id)?;
+			ensure!(!final_price.is_zero(), Error::<T>::InvalidSettlementPrice);
+			let rate = Price::checked_from_rational(
+				final_price.saturated_into::<u128>(),
+				Self::base_unit(currency_id).saturated_into::<u128>(),
+			)
+			.ok_or(Error::<T>::InvalidSettlementPrice)?;
+			let set_aside = SettlementReserves::<T>::iter()
+				.fold(BalanceOf::<T>::zero(), |set_aside, (_, reserve)| set_aside.saturating_add(reserve));
+			let reserve = Self::price_mul_int(rate, Self::total_issuance(currency_id))
+				.min(T::Stp258Native::free_balance(&T::SettlementReserveAccount::get()).saturating_sub(set_aside));
+
+			SettlementPrices::<T>::insert(currency_id, final_price);
+			SettlementReserves::<T>::insert(currency_id, reserve);
+			PegTargets::<T>::remove(currency_id);
+			Self::abort_auctions(currency_id);
+			Self::deposit_event(Event::GlobalSettled(currency_id, final_price));
+			Ok(().into())
+		}
+
+		/// Burn `amount` of the globally settled `currency_id` in exchange
+		/// for its share of the native currency set aside for it in the
+		/// settlement reserve, which is at most its settlement price.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::redeem())]
+		pub fn redeem(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				SettlementPrices::<T>::contains_key(currency_id),
+				Error::<T>::CurrencyNotSettled
+			);
+			Self::ensure_can_withdraw(currency_id, &who, amount)?;
+			let reserve = Self::settlement_reserve(currency_id);
+			let native_amount = multiply_by_rational(
+				reserve.saturated_into(),
+				amount.saturated_into(),
+				Self::total_issuance(currency_id).saturated_into::<u128>().max(1),
+			)
+			.map(|native_amount| native_amount.saturated_into::<BalanceOf<T>>())
+			.unwrap_or_else(|_| Zero::zero());
+			ensure!(!native_amount.is_zero(), Error::<T>::RedeemAmountTooLow);
+
+			let reserve_account = T::SettlementReserveAccount::get();
+			T::Stp258Native::ensure_can_withdraw(&reserve_account, native_amount)?;
+
+			<Self as Stp258Currency<T::AccountId>>::withdraw(currency_id, &who, amount)?;
+			T::Stp258Native::transfer(&reserve_account, &who, native_amount)?;
+			SettlementReserves::<T>::insert(currency_id, reserve.saturating_sub(native_amount));
+
+			Self::deposit_event(Event::Redeemed(
+				currency_id,
+				who,
+				amount,
+				native_amount,
+				Self::total_issuance(currency_id),
+			));
+			Ok(().into())
+		}
+
+		/// Swap `amount` of `from_currency` for `to_currency` at the market
+		/// price, minting and burning against the virtual swap pool of the
+		/// settcurrency. One of the currencies must be the native currency.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::swap())]
+		pub fn swap(
+			origin: OriginFor<T>,
+			from_currency: CurrencyIdOf<T>,
+			to_currency: CurrencyIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+			#[pallet::compact] min_out: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::do_swap(&who, from_currency, to_currency, amount, min_out)?;
+			Ok(().into())
+		}
+
+		/// Add `native_amount` of the native currency and up to
+		/// `max_stable_amount` of `currency_id` to its liquidity pool, in
+		/// the ratio of the pool reserves.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::add_liquidity())]
+		pub fn add_liquidity(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] native_amount: BalanceOf<T>,
+			#[pallet::compact] max_stable_amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::do_add_liquidity(&who, currency_id, native_amount, max_stable_amount)?;
+			Ok(().into())
+		}
+
+		/// Remove `shares` of liquidity from the liquidity pool of
+		/// `currency_id`.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::remove_liquidity())]
+		pub fn remove_liquidity(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] shares: BalanceOf<T>,
+			#[pallet::compact] min_native_amount: BalanceOf<T>,
+			#[pallet::compact] min_stable_amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::do_remove_liquidity(&who, currency_id, shares, min_native_amount, min_stable_amount)?;
+			Ok(().into())
+		}
+
+		/// Swap exactly `amount_in` of `currency_in` for at least `min_out`
+		/// of `currency_out` through a liquidity pool.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::swap_exact_in())]
+		pub fn swap_exact_in(
+			origin: OriginFor<T>,
+			currency_in: CurrencyIdOf<T>,
+			currency_out: CurrencyIdOf<T>,
+			#[pallet::compact] amount_in: BalanceOf<T>,
+			#[pallet::compact] min_out: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::do_swap_exact_in(&who, currency_in, currency_out, amount_in, min_out)?;
+			Ok(().into())
+		}
+
+		/// Swap at most `max_in` of `currency_in` for exactly `amount_out`
+		/// of `currency_out` through a liquidity pool.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::swap_exact_out())]
+		pub fn swap_exact_out(
+			origin: OriginFor<T>,
+			currency_in: CurrencyIdOf<T>,
+			currency_out: CurrencyIdOf<T>,
+			#[pallet::compact] amount_out: BalanceOf<T>,
+			#[pallet::compact] max_in: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::do_swap_exact_out(&who, currency_in, currency_out, amount_out, max_in)?;
+			Ok(().into())
+		}
+
+		/// Place a limit order for `amount` of `currency_id` at `price`,
+		/// the native currency per `base_unit` of `currency_id`. The order
+		/// is matched against the resting orders it crosses, and any
+		/// remainder rests in the order book until `expires_at`.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::place_order(T::MaxOrdersPerSide::get()))]
+		pub fn place_order(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			side: OrderSide,
+			#[pallet::compact] price: BalanceOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+			expires_at: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::do_place_order(&who, currency_id, side, price, amount, expires_at)?;
+			Ok(().into())
+		}
+
+		/// Cancel a resting limit order, releasing its reserved funds.
+		///
+		/// The dispatch origin for this call must be `Signed` by the owner
+		/// of the order.
+		#[pallet::weight(T::WeightInfo::cancel_order())]
+		pub fn cancel_order(origin: OriginFor<T>, order_id: OrderId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let order = Self::orders(order_id).ok_or(Error::<T>::OrderNotFound)?;
+			ensure!(order.owner == who, Error::<T>::NotOrderOwner);
+			Self::remove_order(order_id, &order);
+			Self::deposit_event(Event::OrderCancelled(order_id));
+			Ok(().into())
+		}
+
+		/// Allow `who` to feed prices to the oracle.
+		///
+		/// The dispatch origin of this call must be `FeederOrigin`.
+		#[pallet::weight(T::WeightInfo::add_feeder())]
+		pub fn add_feeder(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			T::FeederOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			Feeders::<T>::try_mutate(|feeders| -> DispatchResult {
+				ensure!(!feeders.contains(&who), Error::<T>::FeederAlreadyAdded);
+				ensure!(
+					feeders.len() < T::MaxFeeders::get() as usize,
+					Error::<T>::TooManyFeeders
+				);
+				feeders.push(who.clone());
+				Ok(())
+			})?;
+			Self::deposit_event(Event::FeederAdded(who));
+			Ok(().into())
+		}
+
+		/// Stop `who` from feeding prices to the oracle. The prices it fed
+		/// no longer count towards the median.
+		///
+		/// The dispatch origin of this call must be `FeederOrigin`.
+		#[pallet::weight(T::WeightInfo::remove_feeder())]
+		pub fn remove_feeder(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			T::FeederOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			Feeders::<T>::try_mutate(|feeders| -> DispatchResult {
+				let index = feeders.iter().position(|feeder| *feeder == who).ok_or(Error::<T>::NotFeeder)?;
+				feeders.remove(index);
+				Ok(())
+			})?;
+			Self::deposit_event(Event::FeederRemoved(who));
+			Ok(().into())
+		}
+
+		/// Feed the price of one whole unit of `currency_id` to the oracle,
+		/// and accept the median of the fresh fed prices as its price.
+		///
+		/// The dispatch origin of this call must be `Signed` by a feeder, or
+		/// root, feeding as the `RootOperatorAccountId`.
+		#[pallet::weight(T::WeightInfo::feed_price(T::MaxFeeders::get()))]
+		pub fn feed_price(origin: OriginFor<T>, currency_id: CurrencyIdOf<T>, price: Price) -> DispatchResultWithPostInfo {
+			let feeder = match ensure_signed(origin.clone()) {
+				Ok(who) => {
+					ensure!(Self::feeders().contains(&who), Error::<T>::NotFeeder);
+					who
+				}
+				Err(_) => {
+					ensure_root(origin)?;
+					T::RootOperatorAccountId::get()
+				}
+			};
+			Self::do_feed_price(&feeder, currency_id, price)?;
+			Ok(().into())
+		}
+
+		/// Bond `amount` of the native currency to vote on prices.
+		///
+		/// The dispatch origin of this call must be `Signed` by a feeder.
+		#[pallet::weight(T::WeightInfo::bond_feeder())]
+		pub fn bond_feeder(origin: OriginFor<T>, #[pallet::compact] amount: BalanceOf<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::feeders().contains(&who), Error::<T>::NotFeeder);
+			Self::reserve(T::GetStp258NativeId::get(), &who, amount)?;
+			FeederBonds::<T>::mutate(&who, |bond| *bond = bond.saturating_add(amount));
+			Self::deposit_event(Event::FeederBonded(who, amount));
+			Ok(().into())
+		}
+
+		/// Unbond `amount` of the native currency bonded to vote on prices.
+		/// It stays reserved, and is still slashed for missed votes, for
+		/// `UnbondingPeriod` blocks before it can be withdrawn. Unbonding
+		/// more restarts the period.
+		///
+		/// The dispatch origin of this call must be `Signed` by the bonded
+		/// account.
+		#[pallet::weight(T::WeightInfo::unbond_feeder())]
+		pub fn unbond_feeder(origin: OriginFor<T>, #[pallet::compact] amount: BalanceOf<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			FeederBonds::<T>::try_mutate(&who, |bond| -> DispatchResult {
+				*bond = bond.checked_sub(&amount).ok_or(Error::<T>::InsufficientFeederBond)?;
+				Ok(())
+			})?;
+			let withdrawable_at =
+				<frame_system::Module<T>>::block_number().saturating_add(T::UnbondingPeriod::get());
+			FeederUnbonding::<T>::mutate(&who, |unbonding| {
+				let unbonding_amount = unbonding
+					.as_ref()
+					.map(|(unbonding_amount, _)| *unbonding_amount)
+					.unwrap_or_else(Zero::zero);
+				*unbonding = Some((unbonding_amount.saturating_add(amount), withdrawable_at));
+			});
+			Self::deposit_event(Event::FeederUnbonded(who, amount));
+			Ok(().into())
+		}
+
+		/// Release the unbonded feeder bond, once its `UnbondingPeriod` is
+		/// over.
+		///
+		/// The dispatch origin of this call must be `Signed` by the bonded
+		/// account.
+		#[pallet::weight(T::WeightInfo::withdraw_feeder_bond())]
+		pub fn withdraw_feeder_bond(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let (amount, withdrawable_at) =
+				Self::feeder_unbonding(&who).ok_or(Error::<T>::InsufficientFeederBond)?;
+			ensure!(
+				<frame_system::Module<T>>::block_number() >= withdrawable_at,
+				Error::<T>::FeederBondUnbonding
+			);
+			FeederUnbonding::<T>::remove(&who);
+			Self::unreserve(T::GetStp258NativeId::get(), &who, amount);
+			Self::deposit_event(Event::FeederBondWithdrawn(who, amount));
+			Ok(().into())
+		}
+
+		/// Commit to a price vote on `currency_id`, to be revealed in the
+		/// next vote period. `hash` is the hash of `(salt, price, who)`.
+		///
+		/// The dispatch origin of this call must be `Signed` by a feeder
+		/// bonding at least `MinFeederBond`.
+		#[pallet::weight(T::WeightInfo::commit_price_vote())]
+		pub fn commit_price_vote(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			hash: T::Hash,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::feeders().contains(&who), Error::<T>::NotFeeder);
+			ensure!(
+				Self::feeder_bond(&who) >= T::MinFeederBond::get(),
+				Error::<T>::InsufficientFeederBond
+			);
+			ensure!(
+				Self::pegged_currencies().contains(&currency_id),
+				Error::<T>::NotPegged
+			);
+			PriceVoteCommits::<T>::insert(currency_id, &who, (Self::vote_period_index(), hash));
+			Self::deposit_event(Event::PriceVoteCommitted(who, currency_id));
+			Ok(().into())
+		}
+
+		/// Reveal the price vote on `currency_id` committed in the previous
+		/// vote period.
+		///
+		/// The dispatch origin of this call must be `Signed` by the feeder
+		/// that committed the vote.
+		#[pallet::weight(T::WeightInfo::reveal_price_vote())]
+		pub fn reveal_price_vote(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			price: Price,
+			salt: u64,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!price.is_zero(), Error::<T>::InvalidFeedPrice);
+			let (period, hash) = Self::price_vote_commit(currency_id, &who).ok_or(Error::<T>::NoPriceVoteCommit)?;
+			ensure!(
+				period.saturating_add(One::one()) == Self::vote_period_index(),
+				Error::<T>::NoPriceVoteCommit
+			);
+			ensure!(
+				hash == T::Hashing::hash_of(&(salt, price, &who)),
+				Error::<T>::PriceVoteMismatch
+			);
+			PriceVoteCommits::<T>::remove(currency_id, &who);
+			PriceVotes::<T>::insert(currency_id, &who, price);
+			Self::deposit_event(Event::PriceVoteRevealed(who, currency_id, price));
+			Ok(().into())
+		}
+
+		/// Burn `amount` of `currency_id` while it is below its peg target,
+		/// in exchange for bonds redeemed at the peg, queued behind the
+		/// bonds bought before. In the `Bonds` SERP mode, only the bonds
+		/// offered by the SERP can be bought.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::buy_bonds(T::MaxBondsPerCurrency::get()))]
+		pub fn buy_bonds(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::do_buy_bonds(&who, currency_id, amount)?;
+			Ok(().into())
+		}
+
+		/// Stake `amount` of the native currency in the staking pool,
+		/// paying out the staking rewards earned so far.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::stake())]
+		pub fn stake(origin: OriginFor<T>, #[pallet::compact] amount: BalanceOf<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), Error::<T>::InvalidStakeAmount);
+			let native_currency_id = T::GetStp258NativeId::get();
+			let stake = Self::stakes(&who);
+			let new_stake = stake.saturating_add(amount);
+			ensure!(
+				Self::free_balance(native_currency_id, &who) >= new_stake,
+				Error::<T>::BalanceTooLow
+			);
+
+			Self::update_stake(&who, stake, new_stake)?;
+			Self::deposit_event(Event::Staked(who, amount));
+			Ok(().into())
+		}
+
+		/// Unstake `amount` of the native currency from the staking pool,
+		/// paying out the staking rewards earned so far.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// staker.
+		#[pallet::weight(T::WeightInfo::unstake())]
+		pub fn unstake(origin: OriginFor<T>, #[pallet::compact] amount: BalanceOf<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), Error::<T>::InvalidStakeAmount);
+			let stake = Self::stakes(&who);
+			let new_stake = stake.checked_sub(&amount).ok_or(Error::<T>::InsufficientStake)?;
+
+			Self::update_stake(&who, stake, new_stake)?;
+			Self::deposit_event(Event::Unstaked(who, amount));
+			Ok(().into())
+		}
+
+		/// Claim the staking rewards earned in `currency_id`.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// staker.
+		#[pallet::weight(T::WeightInfo::claim_staking_rewards())]
+		pub fn claim_staking_rewards(origin: OriginFor<T>, currency_id: CurrencyIdOf<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let stake = Self::stakes(&who);
+			Self::settle_staking_rewards(&who, currency_id, stake, stake)?;
+			Ok(().into())
+		}
+
+		/// Set or clear the savings rate of `currency_id`, per block. A
+		/// higher rate draws demand for a settcurrency trading below its
+		/// peg.
+		///
+		/// The dispatch origin of this call must be `SerpOrigin`.
+		#[pallet::weight(T::WeightInfo::set_savings_rate())]
+		pub fn set_savings_rate(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			rate: Option<Price>,
+		) -> DispatchResultWithPostInfo {
+			T::SerpOrigin::ensure_origin(origin)?;
+			Self::ensure_serpable(T::GetStp258NativeId::get(), currency_id)?;
+			Self::accrue_savings(currency_id);
+			SavingsRates::<T>::mutate(currency_id, |savings_rate| *savings_rate = rate);
+			Self::deposit_event(Event::SavingsRateUpdated(currency_id, rate));
+			Ok(().into())
+		}
+
+		/// Deposit `amount` of `currency_id` into savings, earning the
+		/// savings rate.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::deposit_savings())]
+		pub fn deposit_savings(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::ensure_serpable(T::GetStp258NativeId::get(), currency_id)?;
+			ensure!(!amount.is_zero(), Error::<T>::InvalidSavingsAmount);
+			let chi = Self::accrue_savings(currency_id);
+			let units = multiply_by_rational(amount.saturated_into(), Price::accuracy(), chi.into_inner())
+				.map(|units| units.saturated_into::<BalanceOf<T>>())
+				.unwrap_or_else(|_| Zero::zero());
+			ensure!(!units.is_zero(), Error::<T>::InvalidSavingsAmount);
+
+			<Self as Stp258Currency<T::AccountId>>::transfer(currency_id, &who, &Self::savings_pool_account(), amount)?;
+			SavingsBalances::<T>::mutate(currency_id, &who, |balance| *balance = balance.saturating_add(units));
+			TotalSavings::<T>::mutate(currency_id, |total| *total = total.saturating_add(units));
+			Self::deposit_event(Event::SavingsDeposited(who, currency_id, amount));
+			Ok(().into())
+		}
+
+		/// Withdraw `amount` of `currency_id` from savings.
+		///
+		/// The dispatch origin for this call must be `Signed` by the saver.
+		#[pallet::weight(T::WeightInfo::withdraw_savings())]
+		pub fn withdraw_savings(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), Error::<T>::InvalidSavingsAmount);
+			Self::ensure_not_paused(currency_id)?;
+			let chi = Self::accrue_savings(currency_id);
+			let mut units = multiply_by_rational(amount.saturated_into(), Price::accuracy(), chi.into_inner())
+				.map(|units| units.saturated_into::<BalanceOf<T>>())
+				.unwrap_or_else(|_| Zero::zero());
+			if Self::price_mul_int(chi, units) < amount {
+				units = units.saturating_add(One::one());
+			}
+			let balance = Self::savings_balance(currency_id, &who);
+			let remaining = balance.checked_sub(&units).ok_or(Error::<T>::InsufficientSavings)?;
+
+			// savings stay withdrawable once the currency is settled, to be
+			// redeemed at the settlement price
+			T::Stp258Currency::transfer(currency_id, &Self::savings_pool_account(), &who, amount)?;
+			if remaining.is_zero() {
+				SavingsBalances::<T>::remove(currency_id, &who);
+			} else {
+				SavingsBalances::<T>::insert(currency_id, &who, remaining);
+			}
+			TotalSavings::<T>::mutate(currency_id, |total| *total = total.saturating_sub(units));
+			Self::deposit_event(Event::SavingsWithdrawn(who, currency_id, amount));
+			Ok(().into())
+		}
+
+		/// Bid `amount` of native currency on `auction_id`: the native
+		/// currency offered in an expansion auction, or asked in a
+		/// contraction auction. The bid is reserved until it is outbid or
+		/// the auction is settled.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// bidder.
+		#[pallet::weight(T::WeightInfo::bid_auction())]
+		pub fn bid_auction(
+			origin: OriginFor<T>,
+			auction_id: AuctionId,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::do_bid_auction(&who, auction_id, amount)?;
+			Ok(().into())
+		}
+
+		/// Take `amount` of the settcurrency lot of the descending price
+		/// auction `auction_id` at its current price: minted to the taker
+		/// for native currency in an expansion auction, or burned from the
+		/// taker for native currency in a contraction auction.
+		///
+		/// The dispatch origin for this call must be `Signed` by the taker.
+		#[pallet::weight(T::WeightInfo::take_auction())]
+		pub fn take_auction(
+			origin: OriginFor<T>,
+			auction_id: AuctionId,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::do_take_auction(&who, auction_id, amount)?;
+			Ok(().into())
+		}
+
+		/// Burn `native_amount` of native currency to mint `currency_id`
+		/// worth as much at its peg, less the mint fee.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::mint_sett())]
+		pub fn mint_sett(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] native_amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::do_mint_sett(&who, currency_id, native_amount)?;
+			Ok(().into())
+		}
+
+		/// Burn `amount` of `currency_id` to mint native currency worth as
+		/// much at its peg, less the redemption fee.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::redeem_sett())]
+		pub fn redeem_sett(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::do_redeem_sett(&who, currency_id, amount)?;
+			Ok(().into())
+		}
+
+		/// Swap `amount` of the settcurrency `from_currency` for the
+		/// settcurrency `to_currency` at the market price, burning one and
+		/// minting the other, less the Tobin tax.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::swap_sett())]
+		pub fn swap_sett(
+			origin: OriginFor<T>,
+			from_currency: CurrencyIdOf<T>,
+			to_currency: CurrencyIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+			#[pallet::compact] min_out: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::do_swap_sett(&who, from_currency, to_currency, amount, min_out)?;
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> SerpMarket<T::AccountId> for Pallet<T> {
+	/// Called when `expand_supply` is received from the SERP by the SerpTes 
+	/// through the `on_expand_supply` trigger.
+	/// Implementation should `deposit` the `amount` to `serpup_to`, 
+	/// then `amount` will be slashed from `serpup_from` and update
+	/// `new_supply`. The caller's `quote_price` is ignored, the SERP is
+	/// serped at the quote price of the `Oracle` instead. A no-op within
+	/// the peg band.
+	/// `who` is the account to serp with.
+	fn expand_supply(
+		native_currency_id: Self::CurrencyId, 
+		stable_currency_id: Self::CurrencyId, 
+		expand_by: Self::Balance, 
+		_quote_price: Self::Balance, 
+	) -> DispatchResult {
+		if !expand_by.is_zero() && Self::serp_within_peg_band(native_currency_id, stable_currency_id)? {
+			return Ok(());
+		}
+		Self::do_expand_supply(native_currency_id, stable_currency_id, expand_by)
+	}
+
+	/// Called when `contract_supply` is received from the SERP by the SerpTes 
+	/// through the `on_contract_supply` trigger.
+	/// Implementation should `deposit` the `base_currency_id` (The Native Currency) 
+	/// of `amount` to `serpup_to`, then `amount` will be slashed from `serpup_from` 
+	/// and update `new_supply`. The caller's `quote_price` is ignored, the
+	/// SERP is serped at the quote price of the `Oracle` instead. A no-op
+	/// within the peg band.
+	/// `who` is the account to serp with.
+	fn contract_supply(
+		native_currency_id: Self::CurrencyId, 
+		stable_currency_id: Self::CurrencyId, 
+		contract_by: Self::Balance, 
+		_quote_price: Self::Balance, 
+	) -> DispatchResult {
+		if !contract_by.is_zero() && Self::serp_within_peg_band(native_currency_id, stable_currency_id)? {
+			return Ok(());
+		}
+		Self::do_contract_supply(native_currency_id, stable_currency_id, contract_by)
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Ensure `stable_currency_id` can be serped with `native_currency_id`,
+	/// and report whether it is within its peg band, where a SERP is a
+	/// no-op.
+	fn serp_within_peg_band(
+		native_currency_id: CurrencyIdOf<T>,
+		stable_currency_id: CurrencyIdOf<T>,
+	) -> Result<bool, DispatchError> {
+		Self::ensure_serpable(native_currency_id, stable_currency_id)?;
+		Self::ensure_not_paused(stable_currency_id)?;
+		Self::ensure_not_settled(stable_currency_id)?;
+		let within_band = Self::is_within_peg_band(stable_currency_id);
+		if within_band {
+			Self::deposit_event(Event::SerpWithinBand(stable_currency_id));
+		}
+		Ok(within_band)
+	}
+
+	fn do_expand_supply(
+		native_currency_id: CurrencyIdOf<T>,
+		stable_currency_id: CurrencyIdOf<T>,
+		expand_by: BalanceOf<T>,
+	) -> DispatchResult {
+		if expand_by.is_zero() {
+			return Ok(());
+		}
+		Self::ensure_serpable(native_currency_id, stable_currency_id)?;
+		Self::ensure_not_paused(stable_currency_id)?;
+		Self::ensure_not_settled(stable_currency_id)?;
+		let quote_price = Self::quote_price(stable_currency_id).ok_or(Error::<T>::PriceUnavailable)?;
+		let window = Self::ensure_within_supply_change_limit(stable_currency_id, expand_by)?;
+		// Bonds, rewards and auctions are paid out of the expansion in one
+		// transaction, so that a failure late in it does not leave them
+		// minted.
+		with_transaction_result(|| {
+			let seigniorage = Self::redeem_bonds(stable_currency_id, expand_by)?;
+			let oracle_reward = T::OracleRewardShare::get() * seigniorage;
+			let buffer_reward = Self::stability_buffer_shares(stable_currency_id)
+				.map(|shares| shares.seigniorage * seigniorage)
+				.unwrap_or_else(Zero::zero)
+				.min(seigniorage.saturating_sub(oracle_reward));
+			let staker_reward = Self::reward_stakers(
+				stable_currency_id,
+				(T::StakerShare::get() * seigniorage)
+					.min(seigniorage.saturating_sub(oracle_reward).saturating_sub(buffer_reward)),
+			)?;
+			let serp_amount = Self::distribute_seigniorage(
+				stable_currency_id,
+				seigniorage
+					.saturating_sub(oracle_reward)
+					.saturating_sub(buffer_reward)
+					.saturating_sub(staker_reward),
+			)?;
+			let mode = Self::serp_mode(stable_currency_id);
+			if !serp_amount.is_zero() {
+				match mode {
+					SerpMode::Direct | SerpMode::Bonds => T::Stp258Currency::expand_supply(
+						native_currency_id,
+						stable_currency_id,
+						serp_amount,
+						quote_price,
+					)?,
+					SerpMode::Auction => Self::start_auction(stable_currency_id, AuctionKind::Expansion, serp_amount)?,
+					SerpMode::Dutch(curve) => {
+						Self::start_dutch_auction(stable_currency_id, AuctionKind::Expansion, serp_amount, curve)?
+					}
+				}
+			}
+			if !oracle_reward.is_zero() {
+				T::Stp258Currency::deposit(stable_currency_id, &Self::oracle_reward_pool_account(), oracle_reward)?;
+			}
+			Self::fund_stability_buffer(stable_currency_id, buffer_reward)?;
+			if let Some(window) = window {
+				SupplyChangeWindows::<T>::insert(stable_currency_id, window);
+			}
+			// Auctioned lots are recorded as they are settled.
+			let expanded = match mode {
+				SerpMode::Auction | SerpMode::Dutch(_) => expand_by.saturating_sub(serp_amount),
+				SerpMode::Direct | SerpMode::Bonds => expand_by,
+			};
+			if !expanded.is_zero() {
+				Self::record_supply_adjustment(stable_currency_id, SerpDirection::Expansion, expanded, quote_price);
+				Self::deposit_event(Event::SerpedUpSupply(stable_currency_id, expanded));
+			}
+			Ok(())
+		})
+	}
+
+	fn do_contract_supply(
+		native_currency_id: CurrencyIdOf<T>,
+		stable_currency_id: CurrencyIdOf<T>,
+		contract_by: BalanceOf<T>,
+	) -> DispatchResult {
+		if contract_by.is_zero() {
+			return Ok(());
+		}
+		Self::ensure_serpable(native_currency_id, stable_currency_id)?;
+		Self::ensure_not_paused(stable_currency_id)?;
+		Self::ensure_not_settled(stable_currency_id)?;
+		let quote_price = Self::quote_price(stable_currency_id).ok_or(Error::<T>::PriceUnavailable)?;
+		let mode = Self::serp_mode(stable_currency_id);
+		let buffer_burn = Self::stability_buffer(stable_currency_id).min(contract_by);
+		let serp_amount = contract_by.saturating_sub(buffer_burn);
+		// Offered bonds are checked against the supply change limit and
+		// recorded as they are bought and burned, and auctioned lots are
+		// recorded as they are settled.
+		let (limited, contracted) = match mode {
+			SerpMode::Direct => (contract_by, contract_by),
+			SerpMode::Auction | SerpMode::Dutch(_) => (contract_by, buffer_burn),
+			SerpMode::Bonds => (buffer_burn, buffer_burn),
+		};
+		let window = Self::ensure_within_supply_change_limit(stable_currency_id, limited)?;
+		with_transaction_result(|| {
+			if !buffer_burn.is_zero() {
+				T::Stp258Currency::withdraw(stable_currency_id, &Self::stability_buffer_account(), buffer_burn)?;
+			}
+			if !serp_amount.is_zero() {
+				match mode {
+					SerpMode::Direct => T::Stp258Currency::contract_supply(
+						native_currency_id,
+						stable_currency_id,
+						serp_amount,
+						quote_price,
+					)?,
+					SerpMode::Auction => {
+						Self::start_auction(stable_currency_id, AuctionKind::Contraction, serp_amount)?
+					}
+					SerpMode::Dutch(curve) => {
+						Self::start_dutch_auction(stable_currency_id, AuctionKind::Contraction, serp_amount, curve)?
+					}
+					SerpMode::Bonds => {
+						BondOffers::<T>::mutate(stable_currency_id, |offer| *offer = offer.saturating_add(serp_amount));
+						Self::deposit_event(Event::BondsOffered(stable_currency_id, serp_amount));
+					}
+				}
+			}
+			Ok(())
+		})?;
+		if !buffer_burn.is_zero() {
+			Self::deposit_event(Event::StabilityBufferBurned(stable_currency_id, buffer_burn));
+		}
+		if let Some(window) = window {
+			SupplyChangeWindows::<T>::insert(stable_currency_id, window);
+		}
+		if !contracted.is_zero() {
+			Self::record_supply_adjustment(stable_currency_id, SerpDirection::Contraction, contracted, quote_price);
+			Self::deposit_event(Event::SerpedDownSupply(stable_currency_id, contracted));
+		}
+		Ok(())
+	}
+
+	/// Ensure `stable_currency_id` can be serped against `native_currency_id`.
+	fn ensure_serpable(native_currency_id: CurrencyIdOf<T>, stable_currency_id: CurrencyIdOf<T>) -> DispatchResult {
+		ensure!(
+			native_currency_id == T::GetStp258NativeId::get(),
+			Error::<T>::UnrecognisedNativeCurrency
+		);
+		ensure!(
+			stable_currency_id != T::GetStp258NativeId::get(),
+			Error::<T>::NotSerpable
+		);
+		Ok(())
+	}
+
+	/// Whether `currency_id` is paused.
+	pub fn is_paused(currency_id: CurrencyIdOf<T>) -> bool {
+		PausedCurrencies::<T>::contains_key(currency_id)
+	}
+
+	/// Ensure `currency_id` is not paused.
+	fn ensure_not_paused(currency_id: CurrencyIdOf<T>) -> DispatchResult {
+		ensure!(!Self::is_paused(currency_id), Error::<T>::CurrencyIsPaused);
+		Ok(())
+	}
+
+	/// Ensure `currency_id` is not globally settled.
+	fn ensure_not_settled(currency_id: CurrencyIdOf<T>) -> DispatchResult {
+		ensure!(
+			!SettlementPrices::<T>::contains_key(currency_id),
+			Error::<T>::CurrencySettled
+		);
+		Ok(())
+	}
+
+	/// Ensure changing the supply of `currency_id` by `amount` stays within
+	/// its supply change limit, returning the updated change window.
+	///
+	/// The window is rolling: it holds the changes of the trailing
+	/// `limit.window` blocks, so no more than `limit.max_window_change`
+	/// can pass within any span of that length.
+	///
+	/// Emits `SupplyChangeLimitExceeded` when the limit would be broken.
+	fn ensure_within_supply_change_limit(
+		currency_id: CurrencyIdOf<T>,
+		amount: BalanceOf<T>,
+	) -> result::Result<Option<Vec<(T::BlockNumber, BalanceOf<T>)>>, DispatchError> {
+		let limit = match Self::supply_change_limit(currency_id) {
+			Some(limit) => limit,
+			None => return Ok(None),
+		};
+		let total_issuance = Self::total_issuance(currency_id);
+		if amount > limit.max_single_change * total_issuance {
+			Self::deposit_event(Event::SupplyChangeLimitExceeded(currency_id, amount));
+			return Err(Error::<T>::SupplyChangeTooLarge.into());
+		}
+
+		let now = <frame_system::Module<T>>::block_number();
+		let mut window = Self::supply_change_window(currency_id);
+		window.retain(|(block, _)| block.saturating_add(limit.window) > now);
+		let changed = window
+			.iter()
+			.fold(amount, |changed, (_, change)| changed.saturating_add(*change));
+		if changed > limit.max_window_change * total_issuance {
+			Self::deposit_event(Event::SupplyChangeLimitExceeded(currency_id, amount));
+			return Err(Error::<T>::SupplyChangeRateExceeded.into());
+		}
+		match window.last_mut() {
+			Some((block, change)) if *block == now => *change = change.saturating_add(amount),
+			_ => window.push((now, amount)),
+		}
+		Ok(Some(window))
+	}
+
+	/// The quote price of the native currency in `currency_id`, i.e. how
+	/// many `currency_id` one whole native currency buys. Taken from the
+	/// market prices of both currencies, `None` if either is unavailable.
+	pub fn quote_price(currency_id: CurrencyIdOf<T>) -> Option<BalanceOf<T>> {
+		let market_price = T::Oracle::get_price(currency_id)?;
+		let native_price = T::Oracle::get_price(T::GetStp258NativeId::get())?;
+		let relative_price = native_price.checked_div(&market_price)?;
+		Some(Self::price_mul_int(relative_price, Self::base_unit(currency_id)))
+	}
+
+	/// Whether a price fed or accepted at `at` is still fresh.
+	fn is_fresh_price(at: T::BlockNumber) -> bool {
+		<frame_system::Module<T>>::block_number().saturating_sub(at) <= T::MaxPriceAge::get()
+	}
+
+	/// The oracle price of one whole unit of `currency_id`: its tallied
+	/// price vote median if still fresh, otherwise the median of its fed
+	/// prices if still fresh.
+	pub fn median_price(currency_id: CurrencyIdOf<T>) -> Option<Price> {
+		Self::voted_price(currency_id)
+			.filter(|(_, at)| Self::is_fresh_price(*at))
+			.or_else(|| Self::accepted_price(currency_id).filter(|(_, at)| Self::is_fresh_price(*at)))
+			.map(|(price, _)| price)
+	}
+
+	/// Record the price fed by `feeder` and accept the median of the fresh
+	/// fed prices of `currency_id`, once there are at least `MinFeeds` of
+	/// them.
+	fn do_feed_price(feeder: &T::AccountId, currency_id: CurrencyIdOf<T>, price: Price) -> DispatchResult {
+		ensure!(!price.is_zero(), Error::<T>::InvalidFeedPrice);
+		if let Some(accepted) = Self::median_price(currency_id) {
+			let deviation = price
+				.max(accepted)
+				.saturating_sub(price.min(accepted))
+				.checked_div(&accepted)
+				.unwrap_or_else(Zero::zero);
+			ensure!(
+				deviation <= Price::from(T::MaxPriceDeviation::get()),
+				Error::<T>::PriceDeviationTooLarge
+			);
+		}
+
+		let now = <frame_system::Module<T>>::block_number();
+		RawPrices::<T>::insert(currency_id, feeder, (price, now));
+
+		let mut feeders = Self::feeders();
+		let root_operator = T::RootOperatorAccountId::get();
+		if !feeders.contains(&root_operator) {
+			feeders.push(root_operator);
+		}
+		let mut prices = feeders
+			.into_iter()
+			.filter_map(|feeder| Self::raw_price(currency_id, feeder))
+			.filter(|(_, at)| Self::is_fresh_price(*at))
+			.map(|(price, _)| price)
+			.collect::<Vec<_>>();
+		if prices.len() >= T::MinFeeds::get().max(1) as usize {
+			prices.sort();
+			let middle = prices.len() / 2;
+			let median = if prices.len() % 2 == 0 {
+				prices[middle - 1]
+					.saturating_add(prices[middle])
+					.saturating_mul(Price::saturating_from_rational(1, 2))
+			} else {
+				prices[middle]
+			};
+			AcceptedPrices::<T>::insert(currency_id, (median, now));
+		}
+
+		Self::deposit_event(Event::PriceFed(feeder.clone(), currency_id, price));
+		Ok(())
+	}
+
+	/// The index of the current price vote period.
+	fn vote_period_index() -> T::BlockNumber {
+		let period = T::VotePeriod::get();
+		if period.is_zero() {
+			return Zero::zero();
+		}
+		<frame_system::Module<T>>::block_number() / period
+	}
+
+	/// The account the price vote rewards are paid from.
+	pub fn oracle_reward_pool_account() -> T::AccountId {
+		T::ModuleId::get().into_sub_account(b"orcl")
+	}
+
+	/// Tally the price votes revealed in the vote period ending at `now`,
+	/// accepting their weighted median, rewarding the votes within the
+	/// reward band and counting a miss for every other bonded feeder.
+	fn tally_price_votes(now: T::BlockNumber) -> Weight {
+		let period = T::VotePeriod::get();
+		if period.is_zero() || !(now % period).is_zero() {
+			return T::WeightInfo::tally_price_votes(0);
+		}
+
+		// Every pegged currency ever tallied stays under vote, so that
+		// feeders miss its votes even in a period nobody votes on it, until
+		// it is no longer pegged.
+		let pegged = Self::pegged_currencies();
+		let votes = PriceVotes::<T>::drain()
+			.filter(|(currency_id, _, _)| pegged.contains(currency_id))
+			.collect::<Vec<_>>();
+		let mut currencies = Vec::new();
+		for currency_id in VotedPrices::<T>::iter_keys().collect::<Vec<_>>() {
+			if pegged.contains(&currency_id) {
+				currencies.push(currency_id);
+			} else {
+				VotedPrices::<T>::remove(currency_id);
+			}
+		}
+		for (currency_id, _, _) in votes.iter() {
+			if !currencies.contains(currency_id) {
+				currencies.push(*currency_id);
+			}
+		}
+
+		let slashable_feeders = Self::feeders()
+			.into_iter()
+			.filter(|feeder| !Self::feeder_bond(feeder).is_zero() || Self::feeder_unbonding(feeder).is_some())
+			.collect::<Vec<_>>();
+		for currency_id in currencies.iter().copied() {
+			let mut ballots = votes
+				.iter()
+				.filter(|(vote_currency_id, who, _)| {
+					*vote_currency_id == currency_id
+						&& slashable_feeders.contains(who)
+						&& !Self::feeder_bond(who).is_zero()
+				})
+				.map(|(_, who, price)| (who.clone(), *price, Self::feeder_bond(who)))
+				.collect::<Vec<_>>();
+			if ballots.is_empty() {
+				for feeder in slashable_feeders.iter() {
+					Self::note_missed_vote(feeder);
+				}
+				continue;
+			}
+			ballots.sort_by(|a, b| a.1.cmp(&b.1));
+
+			let total_weight = ballots
+				.iter()
+				.fold(BalanceOf::<T>::zero(), |total, (_, _, weight)| total.saturating_add(*weight));
+			let mut cumulative_weight = BalanceOf::<T>::zero();
+			let mut median = ballots[0].1;
+			for (_, price, weight) in ballots.iter() {
+				cumulative_weight = cumulative_weight.saturating_add(*weight);
+				if cumulative_weight.saturating_add(cumulative_weight) >= total_weight {
+					median = *price;
+					break;
+				}
+			}
+			VotedPrices::<T>::insert(currency_id, (median, now));
+			Self::deposit_event(Event::PriceVoteTallied(currency_id, median));
+
+			let band = Price::from(T::RewardBand::get()).saturating_mul(median);
+			let winners = ballots
+				.into_iter()
+				.filter(|(_, price, _)| {
+					let price = *price;
+					price.max(median).saturating_sub(price.min(median)) <= band
+				})
+				.collect::<Vec<_>>();
+			Self::reward_price_votes(currency_id, &winners);
+			for feeder in slashable_feeders.iter() {
+				if !winners.iter().any(|(who, _, _)| who == feeder) {
+					Self::note_missed_vote(feeder);
+				}
+			}
+		}
+		T::WeightInfo::tally_price_votes(votes.len().saturating_add(currencies.len()) as u32)
+	}
+
+	/// Share the reward pool balance of `currency_id` between `winners`,
+	/// in proportion to their bonds.
+	fn reward_price_votes(currency_id: CurrencyIdOf<T>, winners: &[(T::AccountId, Price, BalanceOf<T>)]) {
+		let pool_account = Self::oracle_reward_pool_account();
+		let pool = Self::free_balance(currency_id, &pool_account);
+		let total_weight = winners
+			.iter()
+			.fold(BalanceOf::<T>::zero(), |total, (_, _, weight)| total.saturating_add(*weight));
+		if pool.is_zero() || total_weight.is_zero() {
+			return;
+		}
+		for (who, _, weight) in winners {
+			let reward = Self::mul_div(pool, *weight, total_weight).unwrap_or_else(Zero::zero);
+			if !reward.is_zero()
+				&& <Self as Stp258Currency<T::AccountId>>::transfer(currency_id, &pool_account, who, reward).is_ok()
+			{
+				Self::deposit_event(Event::FeederRewarded(who.clone(), currency_id, reward));
+			}
+		}
+	}
+
+	/// Count a missed price vote of `who`, slashing its bond, and then its
+	/// unbonding bond, once it missed `MaxMissedVotes` votes.
+	fn note_missed_vote(who: &T::AccountId) {
+		let missed = Self::missed_votes(who).saturating_add(1);
+		if missed < T::MaxMissedVotes::get() {
+			MissedVotes::<T>::insert(who, missed);
+			return;
+		}
+
+		MissedVotes::<T>::remove(who);
+		let bond = Self::feeder_bond(who);
+		let unbonding = Self::feeder_unbonding(who);
+		let unbonding_amount = unbonding.map(|(amount, _)| amount).unwrap_or_else(Zero::zero);
+		let slash = T::MissSlashFraction::get() * bond.saturating_add(unbonding_amount);
+		let unslashed = Self::slash_reserved(T::GetStp258NativeId::get(), who, slash);
+		let slashed = slash.saturating_sub(unslashed);
+		let slashed_bond = slashed.min(bond);
+		FeederBonds::<T>::insert(who, bond.saturating_sub(slashed_bond));
+		if let Some((amount, withdrawable_at)) = unbonding {
+			let amount = amount.saturating_sub(slashed.saturating_sub(slashed_bond));
+			if amount.is_zero() {
+				FeederUnbonding::<T>::remove(who);
+			} else {
+				FeederUnbonding::<T>::insert(who, (amount, withdrawable_at));
+			}
+		}
+		Self::deposit_event(Event::FeederSlashed(who.clone(), slashed));
+	}
+
+	/// Serp every pegged currency towards its peg target, if `now` is an
+	/// adjustment block.
+	fn serp_pegged_currencies(now: T::BlockNumber) -> Weight {
+		let period = T::AdjustmentPeriod::get();
+		if period.is_zero() || !(now % period).is_zero() {
+			return T::WeightInfo::on_initialize(0);
+		}
+
+		let pegged = Self::pegged_currencies();
+		let mut count: u32 = 0;
+		for currency_id in pegged.iter().take(T::MaxAutoSerpsPerBlock::get() as usize) {
+			match Self::peg_price(*currency_id) {
+				Some(peg_target) => Self::serp_to_peg(*currency_id, peg_target),
+				None => Self::deposit_event(Event::SerpSkipped(*currency_id)),
+			}
+			count += 1;
+		}
+		T::WeightInfo::on_initialize(count)
+	}
+
+	/// The peg of `currency_id`: the weighted market prices of its
+	/// constituents if it is a basket, otherwise its peg target.
+	pub fn peg_price(currency_id: CurrencyIdOf<T>) -> Option<Price> {
+		let basket = Self::basket(currency_id);
+		if basket.is_empty() {
+			return Self::peg_target(currency_id);
+		}
+		basket.iter().try_fold(Price::zero(), |peg_price, (constituent, weight)| {
+			let price = T::Oracle::get_price(*constituent)?;
+			Some(peg_price.saturating_add(price.saturating_mul((*weight).into())))
+		})
+	}
+
+	/// Ensure `constituents` is a valid basket for `currency_id`: at most
+	/// `MaxBasketConstituents` distinct settcurrencies that are not baskets,
+	/// weighing 100% in total, or empty.
+	fn ensure_valid_basket(
+		currency_id: CurrencyIdOf<T>,
+		constituents: &[(CurrencyIdOf<T>, Perbill)],
+	) -> DispatchResult {
+		if constituents.is_empty() {
+			return Ok(());
+		}
+		let native_currency_id = T::GetStp258NativeId::get();
+		let total_weight = constituents
+			.iter()
+			.map(|(_, weight)| u64::from(weight.deconstruct()))
+			.sum::<u64>();
+		ensure!(
+			constituents.len() <= T::MaxBasketConstituents::get() as usize
+				&& total_weight == u64::from(Perbill::ACCURACY)
+				&& Self::basket_constituent_count(currency_id).is_zero(),
+			Error::<T>::InvalidBasket
+		);
+		for (index, (constituent, _)) in constituents.iter().enumerate() {
+			ensure!(
+				*constituent != currency_id
+					&& *constituent != native_currency_id
+					&& Self::basket(*constituent).is_empty()
+					&& constituents[..index].iter().all(|(other, _)| other != constituent),
+				Error::<T>::InvalidBasket
+			);
+		}
+		Ok(())
+	}
+
+	/// Expand or contract the supply of `currency_id` in proportion to how
+	/// far its market price is from `peg_target`.
+	fn serp_to_peg(currency_id: CurrencyIdOf<T>, peg_target: Price) {
+		let market_price = match T::Oracle::get_price(currency_id) {
+			Some(market_price) => market_price,
+			None => {
+				Self::deposit_event(Event::SerpSkipped(currency_id));
+				return;
+			}
+		};
+
+		if Self::is_within_peg_band(currency_id) {
+			Self::deposit_event(Event::SerpWithinBand(currency_id));
+			return;
+		}
+
+		let (expand, rate, pid_state) = match Self::pid_gains(currency_id) {
+			Some(gains) => {
+				let (expand, rate, pid_state) =
+					Self::pid_adjustment_rate(currency_id, &gains, market_price, peg_target);
+				(expand, rate, Some(pid_state))
+			}
+			None => {
+				let deviation = market_price
+					.max(peg_target)
+					.saturating_sub(market_price.min(peg_target))
+					.checked_div(&peg_target)
+					.unwrap_or_else(Zero::zero);
+				(market_price > peg_target, deviation, None)
+			}
+		};
+		let adjust_by = Self::price_mul_int(rate, Self::total_issuance(currency_id));
+		if adjust_by.is_zero() {
+			Self::deposit_event(Event::SerpSkipped(currency_id));
+			return;
+		}
+
+		let native_currency_id = T::GetStp258NativeId::get();
+		let result = with_transaction_result(|| {
+			if expand {
+				Self::do_expand_supply(native_currency_id, currency_id, adjust_by)
+			} else {
+				Self::do_contract_supply(native_currency_id, currency_id, adjust_by)
+			}
+		});
+		match result {
+			Ok(()) => {
+				if let Some(pid_state) = pid_state {
+					PidStates::<T>::insert(currency_id, pid_state);
+				}
+			}
+			Err(e) => Self::deposit_event(Event::SerpFailed(currency_id, e)),
+		}
+	}
+
+	/// The currencies serped automatically: those with a peg target or a
+	/// basket. Settled baskets keep their definition, and so their base
+	/// unit, but are no longer serped.
+	fn pegged_currencies() -> Vec<CurrencyIdOf<T>> {
+		PegTargets::<T>::iter()
+			.map(|(currency_id, _)| currency_id)
+			.chain(
+				Baskets::<T>::iter()
+					.map(|(currency_id, _)| currency_id)
+					.filter(|currency_id| {
+						!PegTargets::<T>::contains_key(currency_id) && !SettlementPrices::<T>::contains_key(currency_id)
+					}),
+			)
+			.collect()
+	}
+
+	/// Ensure `currency_id` can be serped automatically without serping
+	/// more than `MaxAutoSerpsPerBlock` currencies in a block, so that every
+	/// pegged currency is serped in every adjustment period.
+	fn ensure_can_auto_serp(currency_id: CurrencyIdOf<T>) -> DispatchResult {
+		let pegged = Self::pegged_currencies();
+		ensure!(
+			pegged.contains(&currency_id) || pegged.len() < T::MaxAutoSerpsPerBlock::get() as usize,
+			Error::<T>::TooManyPeggedCurrencies
+		);
+		Ok(())
+	}
+
+	fn do_buy_bonds(who: &T::AccountId, currency_id: CurrencyIdOf<T>, amount: BalanceOf<T>) -> DispatchResult {
+		Self::ensure_serpable(T::GetStp258NativeId::get(), currency_id)?;
+		Self::ensure_not_settled(currency_id)?;
+		ensure!(
+			!amount.is_zero() && amount >= T::MinBondAmount::get(),
+			Error::<T>::InvalidBondAmount
+		);
+		let peg_target = Self::peg_price(currency_id).ok_or(Error::<T>::NotBelowPeg)?;
+		let market_price = T::Oracle::get_price(currency_id).ok_or(Error::<T>::PriceUnavailable)?;
+		ensure!(market_price < peg_target, Error::<T>::NotBelowPeg);
+		let bond_amount = Self::price_mul_int(
+			peg_target.checked_div(&market_price).ok_or(Error::<T>::PriceUnavailable)?,
+			amount,
+		);
+		ensure!(!bond_amount.is_zero(), Error::<T>::InvalidBondAmount);
+		let bonds_mode = Self::serp_mode(currency_id) == SerpMode::Bonds;
+		ensure!(
+			!bonds_mode || amount <= Self::bond_offer(currency_id),
+			Error::<T>::BondOfferExceeded
+		);
+		let quote_price = Self::quote_price(currency_id).ok_or(Error::<T>::PriceUnavailable)?;
+		let window = Self::ensure_within_supply_change_limit(currency_id, amount)?;
+
+		let bond_id = Self::next_bond_id();
+		let expires_at = T::BondLifetime::get()
+			.map(|lifetime| <frame_system::Module<T>>::block_number().saturating_add(lifetime));
+		with_transaction_result(|| {
+			NextBondId::<T>::put(bond_id.checked_add(1).ok_or(Error::<T>::NoAvailableBondId)?);
+			BondQueues::<T>::try_mutate(currency_id, |queue| -> DispatchResult {
+				Self::prune_expired_bonds(queue);
+				ensure!(
+					queue.len() < T::MaxBondsPerCurrency::get() as usize,
+					Error::<T>::BondQueueFull
+				);
+				queue.push(bond_id);
+				Ok(())
+			})?;
+			<Self as Stp258Currency<T::AccountId>>::withdraw(currency_id, who, amount)?;
+			Bonds::<T>::insert(
+				bond_id,
+				Bond {
+					owner: who.clone(),
+					currency_id,
+					amount: bond_amount,
+					expires_at,
+				},
+			);
+			Ok(())
+		})?;
+
+		if bonds_mode {
+			BondOffers::<T>::mutate(currency_id, |offer| *offer = offer.saturating_sub(amount));
+		}
+		if let Some(window) = window {
+			SupplyChangeWindows::<T>::insert(currency_id, window);
+		}
+		Self::record_supply_adjustment(currency_id, SerpDirection::Contraction, amount, quote_price);
+		Self::deposit_event(Event::BondsBought(bond_id, who.clone(), currency_id, amount, bond_amount));
+		Ok(())
+	}
+
+	/// Drop the expired bonds, and the bonds no longer stored, from a bond
+	/// queue.
+	fn prune_expired_bonds(queue: &mut Vec<BondId>) {
+		let now = <frame_system::Module<T>>::block_number();
+		queue.retain(|bond_id| match Self::bonds(bond_id) {
+			Some(bond) if bond.expires_at.map_or(false, |expires_at| expires_at < now) => {
+				Bonds::<T>::remove(bond_id);
+				Self::deposit_event(Event::BondExpired(*bond_id));
+				false
+			}
+			Some(_) => true,
+			None => false,
+		});
+	}
+
+	/// Redeem the queued bonds of `currency_id` in order out of an
+	/// expansion of `amount`, dropping the expired ones, and return the
+	/// part of `amount` left over.
+	fn redeem_bonds(currency_id: CurrencyIdOf<T>, amount: BalanceOf<T>) -> result::Result<BalanceOf<T>, DispatchError> {
+		let now = <frame_system::Module<T>>::block_number();
+		let mut queue = Self::bond_queue(currency_id);
+		let mut remaining = amount;
+		let mut settled = 0;
+		for bond_id in queue.iter() {
+			if remaining.is_zero() {
+				break;
+			}
+			let mut bond = match Self::bonds(bond_id) {
+				Some(bond) => bond,
+				None => {
+					settled += 1;
+					continue;
+				}
+			};
+			if bond.expires_at.map_or(false, |expires_at| expires_at < now) {
+				Bonds::<T>::remove(bond_id);
+				Self::deposit_event(Event::BondExpired(*bond_id));
+				settled += 1;
+				continue;
+			}
+
+			let redeemed = remaining.min(bond.amount);
+			T::Stp258Currency::deposit(currency_id, &bond.owner, redeemed)?;
+			remaining = remaining.saturating_sub(redeemed);
+			bond.amount = bond.amount.saturating_sub(redeemed);
+			Self::deposit_event(Event::BondRedeemed(*bond_id, bond.owner.clone(), currency_id, redeemed));
+			if bond.amount.is_zero() {
+				Bonds::<T>::remove(bond_id);
+				settled += 1;
+			} else {
+				Bonds::<T>::insert(bond_id, bond);
+			}
+		}
+
+		if settled > 0 {
+			BondQueues::<T>::insert(currency_id, queue.split_off(settled));
+		}
+		Ok(remaining)
+	}
+
+	/// The account the staking rewards are paid from.
+	pub fn staking_pool_account() -> T::AccountId {
+		T::ModuleId::get().into_sub_account(b"stak")
+	}
+
+	/// Pay `amount` of `currency_id` to the stakers, if there are any, and
+	/// return the amount paid.
+	fn reward_stakers(
+		currency_id: CurrencyIdOf<T>,
+		amount: BalanceOf<T>,
+	) -> result::Result<BalanceOf<T>, DispatchError> {
+		let total_staked = Self::total_staked();
+		if amount.is_zero() || total_staked.is_zero() {
+			return Ok(Zero::zero());
+		}
+		let reward_per_share = match Price::checked_from_rational(
+			amount.saturated_into::<u128>(),
+			total_staked.saturated_into::<u128>(),
+		) {
+			Some(reward_per_share) => reward_per_share,
+			None => return Ok(Zero::zero()),
+		};
+
+		T::Stp258Currency::deposit(currency_id, &Self::staking_pool_account(), amount)?;
+		StakingRewardPerShare::<T>::mutate(currency_id, |total| *total = total.saturating_add(reward_per_share));
+		Self::deposit_event(Event::StakersRewarded(currency_id, amount));
+		Ok(amount)
+	}
+
+	/// The staking rewards earned by `who` in `currency_id` and not yet
+	/// paid out.
+	pub fn pending_staking_rewards(who: &T::AccountId, currency_id: CurrencyIdOf<T>) -> BalanceOf<T> {
+		Self::price_mul_int(Self::staking_reward_per_share(currency_id), Self::stakes(who))
+			.saturating_sub(Self::staking_reward_debt(who, currency_id))
+	}
+
+	/// Pay out the staking rewards `who` earned in `currency_id` with
+	/// `stake`, and account for the rewards of `new_stake` as paid.
+	fn settle_staking_rewards(
+		who: &T::AccountId,
+		currency_id: CurrencyIdOf<T>,
+		stake: BalanceOf<T>,
+		new_stake: BalanceOf<T>,
+	) -> DispatchResult {
+		let reward_per_share = Self::staking_reward_per_share(currency_id);
+		let reward = Self::price_mul_int(reward_per_share, stake).saturating_sub(Self::staking_reward_debt(who, currency_id));
+		if !reward.is_zero() {
+			// paid out even while the currency is paused or settled, so that
+			// its rewards never keep a stake locked
+			T::Stp258Currency::transfer(currency_id, &Self::staking_pool_account(), who, reward)?;
+			Self::deposit_event(Event::StakingRewardsClaimed(who.clone(), currency_id, reward));
+		}
+		StakingRewardDebts::<T>::insert(who, currency_id, Self::price_mul_int(reward_per_share, new_stake));
+		Ok(())
+	}
+
+	/// Change the stake of `who` from `stake` to `new_stake`, paying out
+	/// the staking rewards earned in every currency and updating its lock.
+	fn update_stake(who: &T::AccountId, stake: BalanceOf<T>, new_stake: BalanceOf<T>) -> DispatchResult {
+		let native_currency_id = T::GetStp258NativeId::get();
+		with_transaction_result(|| {
+			for (currency_id, _) in StakingRewardPerShare::<T>::iter() {
+				Self::settle_staking_rewards(who, currency_id, stake, new_stake)?;
+			}
+			if new_stake.is_zero() {
+				Self::remove_lock(T::StakingLockId::get(), native_currency_id, who)?;
+				Stakes::<T>::remove(who);
+			} else {
+				Self::set_lock(T::StakingLockId::get(), native_currency_id, who, new_stake)?;
+				Stakes::<T>::insert(who, new_stake);
+			}
+			TotalStaked::<T>::mutate(|total| *total = total.saturating_add(new_stake).saturating_sub(stake));
+			Ok(())
+		})
+	}
+
+	/// The account holding the savings, funded by the seigniorage the
+	/// seigniorage distribution pays to it.
+	pub fn savings_pool_account() -> T::AccountId {
+		T::ModuleId::get().into_sub_account(b"save")
+	}
+
+	/// The value of one unit of savings of `currency_id`, with the interest
+	/// accrued up to the current block. Interest accrues at the savings
+	/// rate, as far as the savings pool holds more than it owes.
+	fn current_savings_chi(currency_id: CurrencyIdOf<T>) -> Price {
+		let now = <frame_system::Module<T>>::block_number();
+		let (chi, last_accrued) = Self::savings_chi(currency_id).unwrap_or_else(|| (Price::one(), now));
+		let total_savings = Self::total_savings(currency_id);
+		let rate = match Self::savings_rate(currency_id) {
+			Some(rate) if now > last_accrued && !total_savings.is_zero() => rate,
+			_ => return chi,
+		};
+
+		let elapsed = now.saturating_sub(last_accrued).saturated_into::<u128>().saturated_into::<usize>();
+		let owed = Self::price_mul_int(chi, total_savings);
+		let interest = Self::price_mul_int(Price::one().saturating_add(rate).saturating_pow(elapsed), owed)
+			.saturating_sub(owed)
+			.min(Self::free_balance(currency_id, &Self::savings_pool_account()).saturating_sub(owed));
+		Price::checked_from_rational(
+			owed.saturating_add(interest).saturated_into::<u128>(),
+			total_savings.saturated_into::<u128>(),
+		)
+		.unwrap_or(chi)
+	}
+
+	/// Accrue the interest on the savings of `currency_id` up to the
+	/// current block, returning the value of one unit of savings.
+	fn accrue_savings(currency_id: CurrencyIdOf<T>) -> Price {
+		let chi = Self::current_savings_chi(currency_id);
+		SavingsChi::<T>::insert(currency_id, (chi, <frame_system::Module<T>>::block_number()));
+		chi
+	}
+
+	/// The savings of `currency_id` held by `who`, with the interest
+	/// accrued up to the current block.
+	pub fn savings_of(currency_id: CurrencyIdOf<T>, who: &T::AccountId) -> BalanceOf<T> {
+		Self::price_mul_int(Self::current_savings_chi(currency_id), Self::savings_balance(currency_id, who))
+	}
+
+	/// The account holding the stability buffers, which absorb supply
+	/// contractions before the serper.
+	pub fn stability_buffer_account() -> T::AccountId {
+		T::ModuleId::get().into_sub_account(b"bufr")
+	}
+
+	/// The balance of the stability buffer of `currency_id`.
+	pub fn stability_buffer(currency_id: CurrencyIdOf<T>) -> BalanceOf<T> {
+		Self::free_balance(currency_id, &Self::stability_buffer_account())
+	}
+
+	/// The stability buffer of `currency_id` relative to its total
+	/// issuance.
+	pub fn stability_buffer_coverage(currency_id: CurrencyIdOf<T>) -> Option<Price> {
+		Price::checked_from_rational(
+			Self::stability_buffer(currency_id).saturated_into::<u128>(),
+			Self::total_issuance(currency_id).saturated_into::<u128>(),
+		)
+	}
+
+	/// Mint `amount` of `currency_id` into its stability buffer.
+	fn fund_stability_buffer(currency_id: CurrencyIdOf<T>, amount: BalanceOf<T>) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(());
+		}
+		T::Stp258Currency::deposit(currency_id, &Self::stability_buffer_account(), amount)?;
+		Self::deposit_event(Event::StabilityBufferFunded(currency_id, amount));
+		Ok(())
+	}
+
+	/// Start an auction minting or burning `lot` of `currency_id`.
+	fn start_auction(currency_id: CurrencyIdOf<T>, kind: AuctionKind, lot: BalanceOf<T>) -> DispatchResult {
+		let value = Self::convert_amount(currency_id, T::GetStp258NativeId::get(), lot)
+			.ok_or(Error::<T>::PriceUnavailable)?;
+		let reserve = match kind {
+			AuctionKind::Expansion => value.max(One::one()),
+			AuctionKind::Contraction => value,
+		};
+		let auction_id = Self::next_auction_id();
+		NextAuctionId::<T>::put(auction_id.checked_add(1).ok_or(Error::<T>::NoAvailableAuctionId)?);
+		let start = <frame_system::Module<T>>::block_number();
+		let end = start.saturating_add(T::AuctionDuration::get());
+		Auctions::<T>::insert(
+			auction_id,
+			Auction {
+				currency_id,
+				kind,
+				lot,
+				reserve,
+				bid: None,
+				start,
+				end,
+			},
+		);
+		AuctionEndTimes::<T>::insert(end, auction_id, ());
+		Self::deposit_event(Event::AuctionStarted(auction_id, currency_id, kind, lot));
+		Ok(())
+	}
+
+	/// The currency and amount reserved for a `bid` on `auction`.
+	fn auction_reserve(auction: &AuctionOf<T>, bid: BalanceOf<T>) -> (CurrencyIdOf<T>, BalanceOf<T>) {
+		match auction.kind {
+			AuctionKind::Expansion => (T::GetStp258NativeId::get(), bid),
+			AuctionKind::Contraction => (auction.currency_id, auction.lot),
+		}
+	}
+
+	/// Whether `amount` improves enough on the best bid of `auction`.
+	fn is_better_bid(auction: &AuctionOf<T>, amount: BalanceOf<T>) -> bool {
+		if amount.is_zero() {
+			return false;
+		}
+		let increment = |bid: BalanceOf<T>| (T::AuctionMinIncrement::get() * bid).max(One::one());
+		match (auction.kind, &auction.bid) {
+			(AuctionKind::Expansion, None) => amount >= auction.reserve,
+			(AuctionKind::Expansion, Some((_, bid))) => amount >= bid.saturating_add(increment(*bid)),
+			(AuctionKind::Contraction, None) => amount <= auction.reserve,
+			(AuctionKind::Contraction, Some((_, ask))) => amount.saturating_add(increment(*ask)) <= *ask,
+		}
+	}
+
+	fn do_bid_auction(who: &T::AccountId, auction_id: AuctionId, amount: BalanceOf<T>) -> DispatchResult {
+		let mut auction = Self::auctions(auction_id).ok_or(Error::<T>::AuctionNotFound)?;
+		Self::ensure_not_paused(auction.currency_id)?;
+		Self::ensure_not_settled(auction.currency_id)?;
+		ensure!(Self::is_better_bid(&auction, amount), Error::<T>::InvalidBid);
+
+		with_transaction_result(|| {
+			if let Some((bidder, bid)) = &auction.bid {
+				let (currency_id, reserved) = Self::auction_reserve(&auction, *bid);
+				Self::unreserve(currency_id, bidder, reserved);
+			}
+			let (currency_id, reserved) = Self::auction_reserve(&auction, amount);
+			Self::reserve(currency_id, who, reserved)
+		})?;
+
+		let now = <frame_system::Module<T>>::block_number();
+		if auction.end.saturating_sub(now) < T::AuctionExtension::get() {
+			let end = now.saturating_add(T::AuctionExtension::get());
+			AuctionEndTimes::<T>::remove(auction.end, auction_id);
+			AuctionEndTimes::<T>::insert(end, auction_id, ());
+			auction.end = end;
+		}
+		auction.bid = Some((who.clone(), amount));
+		Auctions::<T>::insert(auction_id, auction);
+		Self::deposit_event(Event::AuctionBid(auction_id, who.clone(), amount));
+		Ok(())
+	}
+
+	/// Settle the auctions ending at `now` to their best bids. The unsold
+	/// lots, and the lots of paused currencies, are dropped, and their
+	/// supply changes released.
+	fn settle_auctions(now: T::BlockNumber) -> Weight {
+		let ended = AuctionEndTimes::<T>::drain_prefix(now)
+			.map(|(auction_id, _)| auction_id)
+			.collect::<Vec<_>>();
+		for auction_id in ended.iter() {
+			if let Some(auction) = DutchAuctions::<T>::take(auction_id) {
+				Self::release_supply_change(auction.currency_id, auction.start, auction.lot);
+				Self::deposit_event(Event::DutchAuctionEnded(*auction_id, auction.lot));
+				continue;
+			}
+			let auction = match Auctions::<T>::take(auction_id) {
+				Some(auction) => auction,
+				None => continue,
+			};
+			match auction.bid.clone() {
+				Some((winner, bid)) if Self::settle_auction(&auction, &winner, bid).is_ok() => {
+					Self::note_auctioned_supply_change(auction.currency_id, auction.kind, auction.lot, bid);
+					Self::deposit_event(Event::AuctionSettled(*auction_id, winner, bid));
+				}
+				bid => {
+					if let Some((bidder, bid)) = bid {
+						let (currency_id, reserved) = Self::auction_reserve(&auction, bid);
+						Self::unreserve(currency_id, &bidder, reserved);
+					}
+					Self::release_supply_change(auction.currency_id, auction.start, auction.lot);
+					Self::deposit_event(Event::AuctionAborted(*auction_id));
+				}
+			}
+		}
+		T::WeightInfo::settle_auctions(ended.len() as u32)
+	}
+
+	/// Burn the reserved bid of `winner` on `auction` and mint what it
+	/// bought, unless its currency is paused or settled.
+	fn settle_auction(auction: &AuctionOf<T>, winner: &T::AccountId, bid: BalanceOf<T>) -> DispatchResult {
+		Self::ensure_not_paused(auction.currency_id)?;
+		Self::ensure_not_settled(auction.currency_id)?;
+		with_transaction_result(|| {
+			let (currency_id, reserved) = Self::auction_reserve(auction, bid);
+			let unslashed = Self::slash_reserved(currency_id, winner, reserved);
+			ensure!(unslashed.is_zero(), Error::<T>::BalanceTooLow);
+			match auction.kind {
+				AuctionKind::Expansion => T::Stp258Currency::deposit(auction.currency_id, winner, auction.lot),
+				AuctionKind::Contraction => T::Stp258Currency::deposit(T::GetStp258NativeId::get(), winner, bid),
+			}
+		})
+	}
+
+	/// Abort the running auctions of `currency_id`, refunding their bids,
+	/// and end its descending price auctions.
+	fn abort_auctions(currency_id: CurrencyIdOf<T>) {
+		let aborted = Auctions::<T>::iter()
+			.filter(|(_, auction)| auction.currency_id == currency_id)
+			.collect::<Vec<_>>();
+		for (auction_id, auction) in aborted {
+			Auctions::<T>::remove(auction_id);
+			AuctionEndTimes::<T>::remove(auction.end, auction_id);
+			if let Some((bidder, bid)) = &auction.bid {
+				let (currency_id, reserved) = Self::auction_reserve(&auction, *bid);
+				Self::unreserve(currency_id, bidder, reserved);
+			}
+			Self::deposit_event(Event::AuctionAborted(auction_id));
+		}
+
+		let ended = DutchAuctions::<T>::iter()
+			.filter(|(_, auction)| auction.currency_id == currency_id)
+			.collect::<Vec<_>>();
+		for (auction_id, auction) in ended {
+			DutchAuctions::<T>::remove(auction_id);
+			AuctionEndTimes::<T>::remove(auction.end, auction_id);
+			Self::deposit_event(Event::DutchAuctionEnded(auction_id, auction.lot));
+		}
+	}
+
+	/// Record the supply change of `amount` of `currency_id` auctioned for
+	/// `native_amount` of the native currency, at the price it sold at.
+	fn note_auctioned_supply_change(
+		currency_id: CurrencyIdOf<T>,
+		kind: AuctionKind,
+		amount: BalanceOf<T>,
+		native_amount: BalanceOf<T>,
+	) {
+		let quote_price = multiply_by_rational(
+			amount.saturated_into(),
+			Self::base_unit(T::GetStp258NativeId::get()).saturated_into(),
+			native_amount.saturated_into::<u128>().max(1),
+		)
+		.map(|quote_price| quote_price.saturated_into::<BalanceOf<T>>())
+		.unwrap_or_else(|_| Zero::zero());
+		match kind {
+			AuctionKind::Expansion => {
+				Self::record_supply_adjustment(currency_id, SerpDirection::Expansion, amount, quote_price);
+				Self::deposit_event(Event::SerpedUpSupply(currency_id, amount));
+			}
+			AuctionKind::Contraction => {
+				Self::record_supply_adjustment(currency_id, SerpDirection::Contraction, amount, quote_price);
+				Self::deposit_event(Event::SerpedDownSupply(currency_id, amount));
+			}
+		}
+	}
+
+	/// Give back the part of the supply change window of `currency_id`
+	/// taken by the unsold `lot` of an auction started at `start`, if the
+	/// change is still within the window.
+	fn release_supply_change(currency_id: CurrencyIdOf<T>, start: T::BlockNumber, lot: BalanceOf<T>) {
+		SupplyChangeWindows::<T>::mutate_exists(currency_id, |maybe_window| {
+			if let Some(window) = maybe_window {
+				if let Some((_, change)) = window.iter_mut().find(|(block, _)| *block == start) {
+					*change = change.saturating_sub(lot);
+				}
+				window.retain(|(_, change)| !change.is_zero());
+				if window.is_empty() {
+					*maybe_window = None;
+				}
+			}
+		});
+	}
+
+	/// Start a descending price auction minting or burning `lot` of
+	/// `currency_id`, its price falling from a premium on the oracle price
+	/// along `curve`.
+	fn start_dutch_auction(
+		currency_id: CurrencyIdOf<T>,
+		kind: AuctionKind,
+		lot: BalanceOf<T>,
+		curve: DutchCurve,
+	) -> DispatchResult {
+		let native_currency_id = T::GetStp258NativeId::get();
+		let oracle_price = match kind {
+			AuctionKind::Expansion => Self::conversion_rate(currency_id, native_currency_id),
+			AuctionKind::Contraction => Self::conversion_rate(native_currency_id, currency_id),
+		}
+		.ok_or(Error::<T>::PriceUnavailable)?;
+		let start_price = oracle_price.saturating_add(oracle_price.saturating_mul(curve.start_premium.into()));
+		let floor_price = oracle_price.saturating_mul(curve.min_price.into());
+		ensure!(!floor_price.is_zero(), Error::<T>::PriceUnavailable);
+
+		let auction_id = Self::next_auction_id();
+		NextAuctionId::<T>::put(auction_id.checked_add(1).ok_or(Error::<T>::NoAvailableAuctionId)?);
+		let start = <frame_system::Module<T>>::block_number();
+		let end = start.saturating_add(T::AuctionDuration::get());
+		DutchAuctions::<T>::insert(
+			auction_id,
+			DutchAuction {
+				currency_id,
+				kind,
+				lot,
+				start_price,
+				floor_price,
+				curve,
+				start,
+				end,
+			},
+		);
+		AuctionEndTimes::<T>::insert(end, auction_id, ());
+		Self::deposit_event(Event::DutchAuctionStarted(auction_id, currency_id, kind, lot, start_price));
+		Ok(())
+	}
+
+	/// The current price of the descending price auction `auction_id`,
+	/// `None` once it fell below the floor price of the auction.
+	pub fn dutch_auction_price(auction_id: AuctionId) -> Option<Price> {
+		let auction = Self::dutch_auctions(auction_id)?;
+		let elapsed = <frame_system::Module<T>>::block_number()
+			.saturating_sub(auction.start)
+			.saturated_into::<u128>();
+		let decay: Price = auction.curve.decay.into();
+		let price = match auction.curve.kind {
+			PriceDecay::Linear => auction.start_price.saturating_sub(
+				auction
+					.start_price
+					.saturating_mul(decay)
+					.saturating_mul(Price::saturating_from_integer(elapsed)),
+			),
+			PriceDecay::Exponential => auction
+				.start_price
+				.saturating_mul(Price::one().saturating_sub(decay).saturating_pow(elapsed.saturated_into())),
+		};
+		Some(price).filter(|price| *price >= auction.floor_price)
+	}
+
+	fn do_take_auction(who: &T::AccountId, auction_id: AuctionId, amount: BalanceOf<T>) -> DispatchResult {
+		let mut auction = Self::dutch_auctions(auction_id).ok_or(Error::<T>::AuctionNotFound)?;
+		Self::ensure_not_paused(auction.currency_id)?;
+		Self::ensure_not_settled(auction.currency_id)?;
+		ensure!(
+			!amount.is_zero() && amount <= auction.lot,
+			Error::<T>::InvalidTakeAmount
+		);
+		let price = Self::dutch_auction_price(auction_id).ok_or(Error::<T>::AuctionPriceBelowFloor)?;
+		let native_currency_id = T::GetStp258NativeId::get();
+		let native_amount = match auction.kind {
+			AuctionKind::Expansion => Self::price_mul_int(price, amount),
+			AuctionKind::Contraction => {
+				multiply_by_rational(amount.saturated_into(), Price::accuracy(), price.into_inner().max(1))
+					.map(|native_amount| native_amount.saturated_into::<BalanceOf<T>>())
+					.unwrap_or_else(|_| Zero::zero())
+			}
+		};
+		ensure!(!native_amount.is_zero(), Error::<T>::InvalidTakeAmount);
+
+		with_transaction_result(|| {
+			match auction.kind {
+				AuctionKind::Expansion => {
+					T::Stp258Currency::withdraw(native_currency_id, who, native_amount)?;
+					T::Stp258Currency::deposit(auction.currency_id, who, amount)
+				}
+				AuctionKind::Contraction => {
+					T::Stp258Currency::withdraw(auction.currency_id, who, amount)?;
+					T::Stp258Currency::deposit(native_currency_id, who, native_amount)
+				}
+			}
+		})?;
+
+		Self::note_auctioned_supply_change(auction.currency_id, auction.kind, amount, native_amount);
+		auction.lot = auction.lot.saturating_sub(amount);
+		if auction.lot.is_zero() {
+			DutchAuctions::<T>::remove(auction_id);
+			AuctionEndTimes::<T>::remove(auction.end, auction_id);
+		} else {
+			DutchAuctions::<T>::insert(auction_id, auction);
+		}
+		Self::deposit_event(Event::DutchAuctionTaken(auction_id, who.clone(), amount, native_amount));
+		Ok(())
+	}
+
+	/// The amount of `currency_id` one unit of native currency is worth,
+	/// valuing the native currency at its market price and `currency_id`
+	/// at its peg.
+	fn peg_rate(currency_id: CurrencyIdOf<T>) -> Option<Price> {
+		let native_currency_id = T::GetStp258NativeId::get();
+		let native_price = T::Oracle::get_price(native_currency_id)?;
+		let peg_target = Self::peg_price(currency_id).unwrap_or_else(Price::one);
+		let base_rate = Price::checked_from_rational(
+			Self::base_unit(currency_id).saturated_into::<u128>(),
+			Self::base_unit(native_currency_id).saturated_into::<u128>(),
+		)?;
+		native_price.checked_div(&peg_target)?.checked_mul(&base_rate)
+	}
+
+	/// The fee charged on minting and redeeming `currency_id` at its peg,
+	/// rising with the deviation of its market price from the peg.
+	pub fn mint_redeem_fee(currency_id: CurrencyIdOf<T>) -> Perbill {
+		let peg_target = Self::peg_price(currency_id).unwrap_or_else(Price::one);
+		let deviation = T::Oracle::get_price(currency_id)
+			.and_then(|market_price| {
+				market_price
+					.max(peg_target)
+					.saturating_sub(market_price.min(peg_target))
+					.checked_div(&peg_target)
+			})
+			.map(|deviation| Perbill::from_rational_approximation(deviation.into_inner(), Price::accuracy()))
+			.unwrap_or_else(Perbill::zero);
+		T::MintRedeemBaseFee::get().saturating_add(T::MintRedeemFeeSensitivity::get() * deviation)
+	}
+
+	/// Ensure minting or redeeming `amount` more of `currency_id` in this
+	/// block stays within the limit, returning the updated volumes.
+	fn ensure_within_mint_redeem_limit(
+		currency_id: CurrencyIdOf<T>,
+		mint: bool,
+		amount: BalanceOf<T>,
+	) -> result::Result<(T::BlockNumber, BalanceOf<T>, BalanceOf<T>), DispatchError> {
+		let now = <frame_system::Module<T>>::block_number();
+		let (at, minted, redeemed) = Self::mint_redeem_volume(currency_id);
+		let (minted, redeemed) = if at == now {
+			(minted, redeemed)
+		} else {
+			(Zero::zero(), Zero::zero())
+		};
+		let (minted, redeemed) = if mint {
+			(minted.saturating_add(amount), redeemed)
+		} else {
+			(minted, redeemed.saturating_add(amount))
+		};
+		let limit = T::MaxMintRedeemPerBlock::get() * Self::total_issuance(currency_id);
+		ensure!(
+			minted.max(redeemed) <= limit,
+			Error::<T>::MintRedeemLimitExceeded
+		);
+		Ok((now, minted, redeemed))
+	}
+
+	fn do_mint_sett(who: &T::AccountId, currency_id: CurrencyIdOf<T>, native_amount: BalanceOf<T>) -> DispatchResult {
+		Self::ensure_serpable(T::GetStp258NativeId::get(), currency_id)?;
+		Self::ensure_not_paused(currency_id)?;
+		Self::ensure_not_settled(currency_id)?;
+		let rate = Self::peg_rate(currency_id).ok_or(Error::<T>::PriceUnavailable)?;
+		let amount = Self::price_mul_int(rate, native_amount);
+		let fee = Self::mint_redeem_fee(currency_id) * amount;
+		let minted = amount.saturating_sub(fee);
+		ensure!(!minted.is_zero(), Error::<T>::InvalidMintRedeemAmount);
+		let volume = Self::ensure_within_mint_redeem_limit(currency_id, true, amount)?;
+
+		with_transaction_result(|| {
+			T::Stp258Native::withdraw(who, native_amount)?;
+			T::Stp258Currency::deposit(currency_id, who, minted)?;
+			Self::fund_stability_buffer(currency_id, fee)
+		})?;
+		MintRedeemVolumes::<T>::insert(currency_id, volume);
+		Self::deposit_event(Event::SettMinted(who.clone(), currency_id, native_amount, minted, fee));
+		Ok(())
+	}
+
+	fn do_redeem_sett(who: &T::AccountId, currency_id: CurrencyIdOf<T>, amount: BalanceOf<T>) -> DispatchResult {
+		Self::ensure_serpable(T::GetStp258NativeId::get(), currency_id)?;
+		Self::ensure_not_paused(currency_id)?;
+		Self::ensure_not_settled(currency_id)?;
+		let rate = Self::peg_rate(currency_id).ok_or(Error::<T>::PriceUnavailable)?;
+		let fee = Self::mint_redeem_fee(currency_id) * amount;
+		let native_amount = multiply_by_rational(
+			amount.saturating_sub(fee).saturated_into(),
+			Price::accuracy(),
+			rate.into_inner().max(1),
+		)
+		.map(|native_amount| native_amount.saturated_into::<BalanceOf<T>>())
+		.unwrap_or_else(|_| Zero::zero());
+		ensure!(!native_amount.is_zero(), Error::<T>::InvalidMintRedeemAmount);
+		let volume = Self::ensure_within_mint_redeem_limit(currency_id, false, amount)?;
+
+		with_transaction_result(|| {
+			T::Stp258Currency::withdraw(currency_id, who, amount)?;
+			Self::fund_stability_buffer(currency_id, fee)?;
+			T::Stp258Native::deposit(who, native_amount)
+		})?;
+		MintRedeemVolumes::<T>::insert(currency_id, volume);
+		Self::deposit_event(Event::SettRedeemed(who.clone(), currency_id, amount, native_amount, fee));
+		Ok(())
+	}
+
+	fn do_swap_sett(
+		who: &T::AccountId,
+		from_currency: CurrencyIdOf<T>,
+		to_currency: CurrencyIdOf<T>,
+		amount: BalanceOf<T>,
+		min_out: BalanceOf<T>,
+	) -> DispatchResult {
+		let native_currency_id = T::GetStp258NativeId::get();
+		ensure!(
+			from_currency != to_currency && from_currency != native_currency_id && to_currency != native_currency_id,
+			Error::<T>::InvalidSettSwapPair
+		);
+		if amount.is_zero() {
+			return Ok(());
+		}
+		for currency_id in [from_currency, to_currency].iter() {
+			Self::ensure_not_paused(*currency_id)?;
+			Self::ensure_not_settled(*currency_id)?;
+		}
+
+		let tax = T::TobinTax::get() * amount;
+		let amount_out = Self::convert_amount(from_currency, to_currency, amount.saturating_sub(tax))
+			.ok_or(Error::<T>::PriceUnavailable)?;
+		ensure!(!amount_out.is_zero() && amount_out >= min_out, Error::<T>::SlippageExceeded);
+		// the rate actually received, in whole units of `to_currency` per
+		// whole unit of `from_currency`
+		let rate = Price::checked_from_rational(amount_out.saturated_into::<u128>(), amount.saturated_into::<u128>())
+			.and_then(|rate| {
+				rate.checked_mul(&Price::checked_from_rational(
+					Self::base_unit(from_currency).saturated_into::<u128>(),
+					Self::base_unit(to_currency).saturated_into::<u128>(),
+				)?)
+			})
+			.unwrap_or_else(Zero::zero);
+
+		with_transaction_result(|| {
+			T::Stp258Currency::withdraw(from_currency, who, amount)?;
+			Self::fund_stability_buffer(from_currency, tax)?;
+			T::Stp258Currency::deposit(to_currency, who, amount_out)
+		})?;
+		Self::deposit_event(Event::SettSwapped(
+			who.clone(),
+			from_currency,
+			to_currency,
+			amount,
+			amount_out,
+			tax,
+			rate,
+		));
+		Ok(())
+	}
+
+	/// Pay the beneficiaries of the seigniorage distribution of
+	/// `currency_id` their share of `amount`, and return the part of
+	/// `amount` left over.
+	fn distribute_seigniorage(
+		currency_id: CurrencyIdOf<T>,
+		amount: BalanceOf<T>,
+	) -> result::Result<BalanceOf<T>, DispatchError> {
+		let distribution = Self::seigniorage_distribution(currency_id);
+		if distribution.is_empty() || amount.is_zero() {
+			return Ok(amount);
+		}
+
+		let mut remaining = amount;
+		let mut payouts = Vec::with_capacity(distribution.len());
+		for (beneficiary, share) in distribution {
+			let payout = (share * amount).min(remaining);
+			if payout.is_zero() {
+				continue;
+			}
+			T::Stp258Currency::deposit(currency_id, &beneficiary, payout)?;
+			remaining = remaining.saturating_sub(payout);
+			payouts.push((beneficiary, payout));
+		}
+		Self::deposit_event(Event::SeigniorageDistributed(currency_id, payouts));
+		Ok(remaining)
+	}
+
+	/// The position of `bond_id` in the bond queue of its settcurrency and
+	/// the amount of the bonds ahead of it.
+	pub fn bond_queue_position(bond_id: BondId) -> Option<(u32, BalanceOf<T>)> {
+		let bond = Self::bonds(bond_id)?;
+		let queue = Self::bond_queue(bond.currency_id);
+		let position = queue.iter().position(|queued_id| *queued_id == bond_id)?;
+		let ahead = queue[..position]
+			.iter()
+			.filter_map(|queued_id| Self::bonds(queued_id))
+			.fold(BalanceOf::<T>::zero(), |ahead, bond| ahead.saturating_add(bond.amount));
+		Some((position as u32, ahead))
+	}
+
+	/// Whether the market price of `currency_id` is within the peg band
+	/// around its peg target.
+	pub fn is_within_peg_band(currency_id: CurrencyIdOf<T>) -> bool {
+		let within_band = || -> Option<bool> {
+			let band = Self::peg_band(currency_id)?;
+			let peg_target = Self::peg_price(currency_id)?;
+			let market_price = T::Oracle::get_price(currency_id)?;
+			let deviation = market_price
+				.max(peg_target)
+				.saturating_sub(market_price.min(peg_target))
+				.checked_div(&peg_target)?;
+			Some(deviation <= Price::from(band))
+		};
+		within_band().unwrap_or(false)
+	}
+
+	/// Step the PID controller of `currency_id` with the price error of
+	/// `market_price` relative to `peg_target`, returning whether to expand
+	/// the supply, by which part of the total issuance, and the controller
+	/// state to keep once the adjustment succeeds. The first step has no
+	/// previous error, and so no derivative term.
+	fn pid_adjustment_rate(
+		currency_id: CurrencyIdOf<T>,
+		gains: &PidGains,
+		market_price: Price,
+		peg_target: Price,
+	) -> (bool, Price, PidState) {
+		let to_signed = |price: Price| FixedI128::from_inner(price.into_inner().saturated_into());
+		let peg_target = to_signed(peg_target);
+		let error = to_signed(market_price)
+			.saturating_sub(peg_target)
+			.checked_div(&peg_target)
+			.unwrap_or_else(Zero::zero);
+
+		let state = Self::pid_state(currency_id);
+		let integral = state
+			.map(|state| state.integral)
+			.unwrap_or_else(Zero::zero)
+			.saturating_add(error)
+			.min(gains.integral_limit)
+			.max(gains.integral_limit.saturating_mul(FixedI128::saturating_from_integer(-1)));
+		let derivative = state
+			.map(|state| error.saturating_sub(state.last_error))
+			.unwrap_or_else(Zero::zero);
+
+		let output = gains
+			.proportional
+			.saturating_mul(error)
+			.saturating_add(gains.integral.saturating_mul(integral))
+			.saturating_add(gains.derivative.saturating_mul(derivative));
+		let rate = Price::from_inner(output.into_inner().saturating_abs().saturated_into());
+		let state = PidState {
+			integral,
+			last_error: error,
+		};
+		(output.is_positive(), rate, state)
+	}
+
+	/// Convert `amount` of `from_currency` into `to_currency` at the market
+	/// price, if both currencies have one.
+	pub fn convert_amount(
+		from_currency: CurrencyIdOf<T>,
+		to_currency: CurrencyIdOf<T>,
+		amount: BalanceOf<T>,
+	) -> Option<BalanceOf<T>> {
+		Self::conversion_rate(from_currency, to_currency).map(|rate| Self::price_mul_int(rate, amount))
+	}
+
+	/// The amount of `to_currency` one unit of `from_currency` is worth at
+	/// the market price, if both currencies have one.
+	fn conversion_rate(from_currency: CurrencyIdOf<T>, to_currency: CurrencyIdOf<T>) -> Option<Price> {
+		let from_price = T::Oracle::get_price(from_currency)?;
+		let to_price = T::Oracle::get_price(to_currency)?;
+		let base_rate = Price::checked_from_rational(
+			Self::base_unit(to_currency).saturated_into::<u128>(),
+			Self::base_unit(from_currency).saturated_into::<u128>(),
+		)?;
+		from_price.checked_div(&to_price)?.checked_mul(&base_rate)
+	}
+
+	/// The settcurrency of a swap between `currency_in` and
+	/// `currency_out`, and whether the native currency is swapped in.
+	fn swap_pair(
+		currency_in: CurrencyIdOf<T>,
+		currency_out: CurrencyIdOf<T>,
+	) -> result::Result<(CurrencyIdOf<T>, bool), DispatchError> {
+		let native_currency_id = T::GetStp258NativeId::get();
+		ensure!(
+			(currency_in == native_currency_id) != (currency_out == native_currency_id),
+			Error::<T>::InvalidSwapPair
+		);
+		if currency_in == native_currency_id {
+			Ok((currency_out, true))
+		} else {
+			Ok((currency_in, false))
+		}
+	}
+
+	/// Swap between the native currency and a settcurrency against the
+	/// virtual swap pool of the settcurrency.
+	///
+	/// The pool holds `SwapBasePool` of the settcurrency on both sides,
+	/// skewed by its delta. The spread is the constant product slippage of
+	/// the swap through the pool, and at least `MinSwapSpread`.
+	fn do_swap(
+		who: &T::AccountId,
+		from_currency: CurrencyIdOf<T>,
+		to_currency: CurrencyIdOf<T>,
+		amount: BalanceOf<T>,
+		min_out: BalanceOf<T>,
+	) -> DispatchResult {
+		let native_currency_id = T::GetStp258NativeId::get();
+		let (stable_currency_id, offer_native) = Self::swap_pair(from_currency, to_currency)?;
+		Self::ensure_not_paused(stable_currency_id)?;
+		Self::ensure_not_settled(stable_currency_id)?;
+
+		if amount.is_zero() {
+			return Ok(());
+		}
+
+		// the value of the offer, in units of the settcurrency
+		let offer = if offer_native {
+			Self::convert_amount(native_currency_id, stable_currency_id, amount).ok_or(Error::<T>::PriceUnavailable)?
+		} else {
+			amount
+		};
+		let offer = offer.saturated_into::<u128>();
+		let base_pool = T::SwapBasePool::get()
+			.saturating_mul(Self::base_unit(stable_currency_id))
+			.saturated_into::<u128>();
+		let delta = Self::swap_pool_delta(stable_currency_id);
+		let stable_pool = i128::try_from(base_pool).unwrap_or(i128::MAX).saturating_add(delta);
+		ensure!(stable_pool > 0, Error::<T>::SwapPoolDepleted);
+		let stable_pool = stable_pool as u128;
+		let native_pool = multiply_by_rational(base_pool, base_pool, stable_pool).map_err(|_| Error::<T>::SwapPoolDepleted)?;
+
+		let (offer_pool, ask_pool) = if offer_native {
+			(native_pool, stable_pool)
+		} else {
+			(stable_pool, native_pool)
+		};
+		let ask = ask_pool.saturating_sub(
+			multiply_by_rational(base_pool, base_pool, offer_pool.saturating_add(offer))
+				.map_err(|_| Error::<T>::SwapPoolDepleted)?,
+		);
+		let spread = Perbill::from_rational_approximation(offer.saturating_sub(ask), offer.max(1))
+			.max(T::MinSwapSpread::get());
+		let fee = spread * offer;
+		let (amount_out, fee) = if offer_native {
+			(offer.saturating_sub(fee).saturated_into(), fee.saturated_into())
+		} else {
+			(
+				Self::convert_amount(stable_currency_id, native_currency_id, offer.saturating_sub(fee).saturated_into())
+					.ok_or(Error::<T>::PriceUnavailable)?,
+				Self::convert_amount(stable_currency_id, native_currency_id, fee.saturated_into())
+					.ok_or(Error::<T>::PriceUnavailable)?,
+			)
+		};
+		ensure!(amount_out >= min_out, Error::<T>::SlippageExceeded);
+		let buffer_fee = match Self::stability_buffer_shares(stable_currency_id) {
+			Some(shares) if to_currency == stable_currency_id => shares.fees * fee,
+			_ => Zero::zero(),
+		};
+
+		let offer = i128::try_from(offer).unwrap_or(i128::MAX);
+		let new_delta = if offer_native {
+			delta.saturating_sub(offer)
+		} else {
+			delta.saturating_add(offer)
+		};
+
+		with_transaction_result(|| {
+			<Self as Stp258Currency<T::AccountId>>::withdraw(from_currency, who, amount)?;
+			<Self as Stp258Currency<T::AccountId>>::deposit(to_currency, who, amount_out)?;
+			<Self as Stp258Currency<T::AccountId>>::deposit(to_currency, &T::GetSerperAcc::get(), fee.saturating_sub(buffer_fee))?;
+			Self::fund_stability_buffer(to_currency, buffer_fee)?;
+			SwapPoolDeltas::<T>::insert(stable_currency_id, new_delta);
+			Ok(())
+		})?;
+
+		if offer_native {
+			Self::record_trade(stable_currency_id, amount_out, amount);
+		} else {
+			Self::record_trade(stable_currency_id, amount, amount_out);
+		}
+
+		Self::deposit_event(Event::Swapped(
+			who.clone(),
+			from_currency,
+			to_currency,
+			amount,
+			amount_out,
+			fee,
+		));
+		Ok(())
+	}
+
+	/// Move the delta of every swap pool `1 / PoolRecoveryPeriod` of the
+	/// way back to zero.
+	fn recover_swap_pools() -> Weight {
+		let period = i128::try_from(T::PoolRecoveryPeriod::get().saturated_into::<u128>()).unwrap_or(i128::MAX);
+		let mut count: u32 = 0;
+		SwapPoolDeltas::<T>::translate(|_, delta: i128| {
+			count += 1;
+			let recovery = if period == 0 { delta } else { delta / period };
+			if recovery == 0 {
+				None
+			} else {
+				Some(delta - recovery)
+			}
+		});
+		T::WeightInfo::recover_swap_pools(count)
+	}
+
+	/// The account holding the liquidity pool reserves of `currency_id`.
+	pub fn pool_account(currency_id: CurrencyIdOf<T>) -> T::AccountId {
+		T::ModuleId::get().into_sub_account(currency_id)
+	}
+
+	/// The quote price of the native currency in `currency_id` on its
+	/// liquidity pool, i.e. how many `currency_id` one whole native
+	/// currency buys, if the pool has liquidity.
+	pub fn pool_quote_price(currency_id: CurrencyIdOf<T>) -> Option<BalanceOf<T>> {
+		let (native_reserve, stable_reserve) = Self::liquidity_pool(currency_id);
+		Self::mul_div(stable_reserve, Self::base_unit(T::GetStp258NativeId::get()), native_reserve)
+	}
+
+	fn do_add_liquidity(
+		who: &T::AccountId,
+		currency_id: CurrencyIdOf<T>,
+		native_amount: BalanceOf<T>,
+		max_stable_amount: BalanceOf<T>,
+	) -> DispatchResult {
+		let native_currency_id = T::GetStp258NativeId::get();
+		Self::ensure_serpable(native_currency_id, currency_id)?;
+		ensure!(
+			!native_amount.is_zero() && !max_stable_amount.is_zero(),
+			Error::<T>::InvalidLiquidityAmount
+		);
+
+		let (native_reserve, stable_reserve) = Self::liquidity_pool(currency_id);
+		let total_shares = Self::total_liquidity_shares(currency_id);
+		let (stable_amount, shares) = if total_shares.is_zero() {
+			let shares = native_amount
+				.saturated_into::<u128>()
+				.saturating_mul(max_stable_amount.saturated_into::<u128>())
+				.integer_sqrt();
+			(max_stable_amount, shares.saturated_into())
+		} else {
+			let stable_amount =
+				Self::mul_div(native_amount, stable_reserve, native_reserve).ok_or(Error::<T>::InsufficientLiquidity)?;
+			ensure!(stable_amount <= max_stable_amount, Error::<T>::SlippageExceeded);
+			let shares =
+				Self::mul_div(native_amount, total_shares, native_reserve).ok_or(Error::<T>::InsufficientLiquidity)?;
+			(stable_amount, shares)
+		};
+		ensure!(!shares.is_zero(), Error::<T>::InvalidLiquidityAmount);
+
+		let pool_account = Self::pool_account(currency_id);
+		with_transaction_result(|| {
+			<Self as Stp258Currency<T::AccountId>>::transfer(native_currency_id, who, &pool_account, native_amount)?;
+			<Self as Stp258Currency<T::AccountId>>::transfer(currency_id, who, &pool_account, stable_amount)?;
+			LiquidityPools::<T>::insert(
+				currency_id,
+				(
+					native_reserve.saturating_add(native_amount),
+					stable_reserve.saturating_add(stable_amount),
+				),
+			);
+			LiquidityShares::<T>::mutate(currency_id, who, |balance| *balance = balance.saturating_add(shares));
+			TotalLiquidityShares::<T>::insert(currency_id, total_shares.saturating_add(shares));
+			Ok(())
+		})?;
+
+		Self::deposit_event(Event::LiquidityAdded(
+			who.clone(),
+			currency_id,
+			native_amount,
+			stable_amount,
+			shares,
+		));
+		Ok(())
+	}
+
+	fn do_remove_liquidity(
+		who: &T::AccountId,
+		currency_id: CurrencyIdOf<T>,
+		shares: BalanceOf<T>,
+		min_native_amount: BalanceOf<T>,
+		min_stable_amount: BalanceOf<T>,
+	) -> DispatchResult {
+		ensure!(!shares.is_zero(), Error::<T>::InvalidLiquidityAmount);
+		ensure!(
+			Self::liquidity_shares(currency_id, who) >= shares,
+			Error::<T>::InsufficientLiquidityShares
+		);
+
+		let (native_reserve, stable_reserve) = Self::liquidity_pool(currency_id);
+		let total_shares = Self::total_liquidity_shares(currency_id);
+		let native_amount =
+			Self::mul_div(shares, native_reserve, total_shares).ok_or(Error::<T>::InsufficientLiquidity)?;
+		let stable_amount =
+			Self::mul_div(shares, stable_reserve, total_shares).ok_or(Error::<T>::InsufficientLiquidity)?;
+		ensure!(
+			native_amount >= min_native_amount && stable_amount >= min_stable_amount,
+			Error::<T>::SlippageExceeded
+		);
+
+		let pool_account = Self::pool_account(currency_id);
+		with_transaction_result(|| {
+			<Self as Stp258Currency<T::AccountId>>::transfer(
+				T::GetStp258NativeId::get(),
+				&pool_account,
+				who,
+				native_amount,
+			)?;
+			<Self as Stp258Currency<T::AccountId>>::transfer(currency_id, &pool_account, who, stable_amount)?;
+			LiquidityPools::<T>::insert(
+				currency_id,
+				(native_reserve - native_amount, stable_reserve - stable_amount),
+			);
+			LiquidityShares::<T>::mutate(currency_id, who, |balance| *balance = balance.saturating_sub(shares));
+			TotalLiquidityShares::<T>::insert(currency_id, total_shares - shares);
+			Ok(())
+		})?;
+
+		Self::deposit_event(Event::LiquidityRemoved(
+			who.clone(),
+			currency_id,
+			native_amount,
+			stable_amount,
+			shares,
+		));
+		Ok(())
+	}
+
+	/// The reserves of the liquidity pool of `currency_id`, as
+	/// `(reserve_in, reserve_out)` for a swap in the given direction.
+	fn pool_reserves(currency_id: CurrencyIdOf<T>, native_in: bool) -> (BalanceOf<T>, BalanceOf<T>) {
+		let (native_reserve, stable_reserve) = Self::liquidity_pool(currency_id);
+		if native_in {
+			(native_reserve, stable_reserve)
+		} else {
+			(stable_reserve, native_reserve)
+		}
+	}
+
+	fn do_swap_exact_in(
+		who: &T::AccountId,
+		currency_in: CurrencyIdOf<T>,
+		currency_out: CurrencyIdOf<T>,
+		amount_in: BalanceOf<T>,
+		min_out: BalanceOf<T>,
+	) -> DispatchResult {
+		let (currency_id, native_in) = Self::swap_pair(currency_in, currency_out)?;
+		let (reserve_in, reserve_out) = Self::pool_reserves(currency_id, native_in);
+		ensure!(
+			!reserve_in.is_zero() && !reserve_out.is_zero(),
+			Error::<T>::InsufficientLiquidity
+		);
+
+		let amount_in_after_fee = amount_in.saturating_sub(T::PoolSwapFee::get() * amount_in);
+		let amount_out = Self::mul_div(reserve_out, amount_in_after_fee, reserve_in.saturating_add(amount_in_after_fee))
+			.ok_or(Error::<T>::InsufficientLiquidity)?;
+		ensure!(!amount_out.is_zero(), Error::<T>::InsufficientLiquidity);
+		ensure!(amount_out >= min_out, Error::<T>::SlippageExceeded);
+
+		Self::do_pool_swap(who, currency_id, native_in, currency_in, currency_out, amount_in, amount_out)
+	}
+
+	fn do_swap_exact_out(
+		who: &T::AccountId,
+		currency_in: CurrencyIdOf<T>,
+		currency_out: CurrencyIdOf<T>,
+		amount_out: BalanceOf<T>,
+		max_in: BalanceOf<T>,
+	) -> DispatchResult {
+		let (currency_id, native_in) = Self::swap_pair(currency_in, currency_out)?;
+		let (reserve_in, reserve_out) = Self::pool_reserves(currency_id, native_in);
+		ensure!(
+			!reserve_in.is_zero() && !amount_out.is_zero() && amount_out < reserve_out,
+			Error::<T>::InsufficientLiquidity
+		);
+
+		let amount_in_after_fee = Self::mul_div(reserve_in, amount_out, reserve_out - amount_out)
+			.ok_or(Error::<T>::InsufficientLiquidity)?
+			.saturating_add(One::one());
+		let amount_in = Perbill::from_percent(100)
+			.saturating_sub(T::PoolSwapFee::get())
+			.saturating_reciprocal_mul_ceil(amount_in_after_fee);
+		ensure!(amount_in <= max_in, Error::<T>::SlippageExceeded);
+
+		Self::do_pool_swap(who, currency_id, native_in, currency_in, currency_out, amount_in, amount_out)
+	}
+
+	/// Move the funds of a liquidity pool swap and update the pool
+	/// reserves.
+	fn do_pool_swap(
+		who: &T::AccountId,
+		currency_id: CurrencyIdOf<T>,
+		native_in: bool,
+		currency_in: CurrencyIdOf<T>,
+		currency_out: CurrencyIdOf<T>,
+		amount_in: BalanceOf<T>,
+		amount_out: BalanceOf<T>,
+	) -> DispatchResult {
+		let (reserve_in, reserve_out) = Self::pool_reserves(currency_id, native_in);
+		let reserve_in = reserve_in.saturating_add(amount_in);
+		let reserve_out = reserve_out.saturating_sub(amount_out);
+		let pool_account = Self::pool_account(currency_id);
+
+		with_transaction_result(|| {
+			<Self as Stp258Currency<T::AccountId>>::transfer(currency_in, who, &pool_account, amount_in)?;
+			<Self as Stp258Currency<T::AccountId>>::transfer(currency_out, &pool_account, who, amount_out)?;
+			if native_in {
+				LiquidityPools::<T>::insert(currency_id, (reserve_in, reserve_out));
+			} else {
+				LiquidityPools::<T>::insert(currency_id, (reserve_out, reserve_in));
+			}
+			Ok(())
+		})?;
+
+		if native_in {
+			Self::record_trade(currency_id, amount_out, amount_in);
+		} else {
+			Self::record_trade(currency_id, amount_in, amount_out);
+		}
+
+		Self::deposit_event(Event::PoolSwapped(
+			who.clone(),
+			currency_in,
+			currency_out,
+			amount_in,
+			amount_out,
+		));
+		Ok(())
+	}
+
+	/// The currency reserved for `order`.
+	fn order_reserve_currency(order: &OrderOf<T>) -> CurrencyIdOf<T> {
+		match order.side {
+			OrderSide::Bid => T::GetStp258NativeId::get(),
+			OrderSide::Ask => order.currency_id,
+		}
+	}
+
+	fn do_place_order(
+		who: &T::AccountId,
+		currency_id: CurrencyIdOf<T>,
+		side: OrderSide,
+		price: BalanceOf<T>,
+		amount: BalanceOf<T>,
+		expires_at: T::BlockNumber,
+	) -> DispatchResult {
+		Self::ensure_serpable(T::GetStp258NativeId::get(), currency_id)?;
+		Self::ensure_not_paused(currency_id)?;
+		Self::ensure_not_settled(currency_id)?;
+		ensure!(
+			!price.is_zero() && !amount.is_zero() && expires_at > <frame_system::Module<T>>::block_number(),
+			Error::<T>::InvalidOrder
+		);
+		let reserved = match side {
+			OrderSide::Bid => Self::mul_div(amount, price, Self::base_unit(currency_id)).unwrap_or_else(Zero::zero),
+			OrderSide::Ask => amount,
+		};
+		ensure!(!reserved.is_zero(), Error::<T>::InvalidOrder);
+
+		let order_id = Self::next_order_id();
+		let mut order = Order {
+			owner: who.clone(),
+			currency_id,
+			side,
+			price,
+			amount,
+			reserved,
+			expires_at,
+		};
+
+		with_transaction_result(|| {
+			NextOrderId::<T>::put(order_id.checked_add(1).ok_or(Error::<T>::NoAvailableOrderId)?);
+			Self::reserve(Self::order_reserve_currency(&order), who, reserved)?;
+			Self::deposit_event(Event::OrderPlaced(order_id, who.clone(), currency_id, side, price, amount));
+
+			Self::match_order(order_id, &mut order)?;
+			if order.amount.is_zero() {
+				Self::unreserve(Self::order_reserve_currency(&order), who, order.reserved);
+				return Ok(());
+			}
+
+			OrderBooks::<T>::try_mutate(currency_id, side, |book| -> DispatchResult {
+				ensure!(
+					book.len() < T::MaxOrdersPerSide::get() as usize,
+					Error::<T>::OrderBookFull
+				);
+				let index = book
+					.iter()
+					.position(|(resting_price, _)| match side {
+						OrderSide::Bid => *resting_price < price,
+						OrderSide::Ask => *resting_price > price,
+					})
+					.unwrap_or_else(|| book.len());
+				book.insert(index, (price, order_id));
+				Ok(())
+			})?;
+			OrderExpiries::<T>::insert(expires_at, order_id, ());
+			Orders::<T>::insert(order_id, order);
+			Ok(())
+		})
+	}
+
+	/// Fill `taker` against the resting orders on the opposite side of the
+	/// book it crosses, best price first, at the price of the resting
+	/// order.
+	fn match_order(taker_id: OrderId, taker: &mut OrderOf<T>) -> DispatchResult {
+		let native_currency_id = T::GetStp258NativeId::get();
+		let base_unit = Self::base_unit(taker.currency_id);
+		let maker_side = taker.side.opposite();
+		let mut book = Self::order_book(taker.currency_id, maker_side);
+
+		let mut filled_makers = 0;
+		for (maker_price, maker_id) in book.iter() {
+			let crosses = match taker.side {
+				OrderSide::Bid => *maker_price <= taker.price,
+				OrderSide::Ask => *maker_price >= taker.price,
+			};
+			if taker.amount.is_zero() || !crosses {
+				break;
+			}
+			let mut maker = match Self::orders(maker_id) {
+				Some(maker) => maker,
+				None => {
+					filled_makers += 1;
+					continue;
+				}
+			};
+
+			let amount = taker.amount.min(maker.amount);
+			let native_amount = Self::mul_div(amount, *maker_price, base_unit).unwrap_or_else(Zero::zero);
+			let (buyer, seller) = match taker.side {
+				OrderSide::Bid => (taker.owner.clone(), maker.owner.clone()),
+				OrderSide::Ask => (maker.owner.clone(), taker.owner.clone()),
+			};
+			Self::repatriate_reserved(taker.currency_id, &seller, &buyer, amount, BalanceStatus::Free)?;
+			Self::repatriate_reserved(native_currency_id, &buyer, &seller, native_amount, BalanceStatus::Free)?;
+
+			let (buyer_order, seller_order) = match taker.side {
+				OrderSide::Bid => (&mut *taker, &mut maker),
+				OrderSide::Ask => (&mut maker, &mut *taker),
+			};
+			buyer_order.reserved = buyer_order.reserved.saturating_sub(native_amount);
+			seller_order.reserved = seller_order.reserved.saturating_sub(amount);
+			taker.amount = taker.amount.saturating_sub(amount);
+			maker.amount = maker.amount.saturating_sub(amount);
+			Self::record_trade(taker.currency_id, amount, native_amount);
+			Self::deposit_event(Event::OrderFilled(
+				*maker_id,
+				taker_id,
+				taker.currency_id,
+				amount,
+				native_amount,
+			));
+
+			if maker.amount.is_zero() {
+				Self::unreserve(Self::order_reserve_currency(&maker), &maker.owner, maker.reserved);
+				Orders::<T>::remove(maker_id);
+				OrderExpiries::<T>::remove(maker.expires_at, maker_id);
+				filled_makers += 1;
+			} else {
+				Orders::<T>::insert(maker_id, maker);
+			}
+		}
+
+		if filled_makers > 0 {
+			OrderBooks::<T>::insert(taker.currency_id, maker_side, book.split_off(filled_makers));
+		}
+		Ok(())
+	}
+
+	/// Remove a resting order from the book and release its reserved funds.
+	fn remove_order(order_id: OrderId, order: &OrderOf<T>) {
+		Orders::<T>::remove(order_id);
+		OrderExpiries::<T>::remove(order.expires_at, order_id);
+		OrderBooks::<T>::mutate(order.currency_id, order.side, |book| {
+			book.retain(|(_, resting_id)| *resting_id != order_id)
+		});
+		Self::unreserve(Self::order_reserve_currency(order), &order.owner, order.reserved);
+	}
+
+	/// Remove the orders expiring at `now`.
+	fn expire_orders(now: T::BlockNumber) -> Weight {
+		let expired = OrderExpiries::<T>::drain_prefix(now)
+			.map(|(order_id, _)| order_id)
+			.collect::<Vec<_>>();
+		for order_id in expired.iter() {
+			if let Some(order) = Self::orders(order_id) {
+				Self::remove_order(*order_id, &order);
+				Self::deposit_event(Event::OrderExpired(*order_id));
+			}
+		}
+		T::WeightInfo::expire_orders(expired.len() as u32)
+	}
+
+	/// Calculate `a * b / c`, or `None` if `c` is zero or the result
+	/// overflows.
+	fn mul_div(a: BalanceOf<T>, b: BalanceOf<T>, c: BalanceOf<T>) -> Option<BalanceOf<T>> {
+		if c.is_zero() {
+			return None;
+		}
+		multiply_by_rational(a.saturated_into(), b.saturated_into(), c.saturated_into())
+			.ok()
+			.and_then(|result| BalanceOf::<T>::try_from(result).ok())
+	}
+
+	/// Record the price of a trade of `stable_amount` of `currency_id` for
+	/// `native_amount` of the native currency. Trades of less than one
+	/// `base_unit` of the settcurrency are not recorded, as they would move
+	/// the observed price almost for free.
+	fn record_trade(currency_id: CurrencyIdOf<T>, stable_amount: BalanceOf<T>, native_amount: BalanceOf<T>) {
+		if native_amount.is_zero() || stable_amount < Self::base_unit(currency_id) {
+			return;
+		}
+		let native_currency_id = T::GetStp258NativeId::get();
+		let price = Price::checked_from_rational(
+			native_amount
+				.saturated_into::<u128>()
+				.saturating_mul(Self::base_unit(currency_id).saturated_into::<u128>()),
+			stable_amount
+				.saturated_into::<u128>()
+				.saturating_mul(Self::base_unit(native_currency_id).saturated_into::<u128>()),
+		);
+		if let Some(price) = price {
+			Self::record_price_observation(currency_id, native_currency_id, price, stable_amount);
+		}
+	}
+
+	/// Accumulate the price of the pair `currency_a`/`currency_b` up to the
+	/// current block and observe a trade of `volume` of `currency_a` at
+	/// `price`, weighting it by volume against the other trades of the
+	/// block. The oldest observations beyond `MaxPriceObservations` are
+	/// dropped.
+	fn record_price_observation(
+		currency_a: CurrencyIdOf<T>,
+		currency_b: CurrencyIdOf<T>,
+		price: Price,
+		volume: BalanceOf<T>,
+	) {
+		let now = <frame_system::Module<T>>::block_number();
+		let max_observations = T::MaxPriceObservations::get() as usize;
+		PriceObservations::<T>::mutate(currency_a, currency_b, |observations| {
+			let cumulative = observations
+				.last()
+				.map(|last| Self::cumulative_price_at(last, now))
+				.unwrap_or_else(Zero::zero);
+			match observations.last_mut() {
+				Some(last) if last.at == now => {
+					let total_volume = last.volume.saturating_add(volume);
+					let weight = Price::checked_from_rational(
+						volume.saturated_into::<u128>(),
+						total_volume.saturated_into::<u128>(),
+					)
+					.unwrap_or_else(Zero::zero);
+					last.price = last
+						.price
+						.saturating_mul(Price::one().saturating_sub(weight))
+						.saturating_add(price.saturating_mul(weight));
+					last.volume = total_volume;
+				}
+				_ => observations.push(PriceObservation {
+					at: now,
+					cumulative,
+					price,
+					volume,
+				}),
+			}
+			if observations.len() > max_observations {
+				let excess = observations.len() - max_observations;
+				*observations = observations.split_off(excess);
+			}
+		});
+	}
+
+	/// The sum of the price over every block before `at`, extrapolated from
+	/// an earlier `observation`.
+	fn cumulative_price_at(observation: &PriceObservation<T::BlockNumber, BalanceOf<T>>, at: T::BlockNumber) -> Price {
+		let elapsed = Price::saturating_from_integer(at.saturating_sub(observation.at).saturated_into::<u128>());
+		observation
+			.cumulative
+			.saturating_add(observation.price.saturating_mul(elapsed))
+	}
+
+	/// The time-weighted average price of one whole unit of `currency_a` in
+	/// whole units of `currency_b`, over the last `window` blocks.
+	///
+	/// Returns `None` if the pair has not been traded, or its observations
+	/// do not cover the whole window.
+	pub fn twap(currency_a: CurrencyIdOf<T>, currency_b: CurrencyIdOf<T>, window: T::BlockNumber) -> Option<Price> {
+		Self::pair_twap(currency_a, currency_b, window)
+			.or_else(|| Self::pair_twap(currency_b, currency_a, window).and_then(|price| price.reciprocal()))
+	}
+
+	/// The time-weighted average price of the pair as observed, see `twap`.
+	fn pair_twap(currency_a: CurrencyIdOf<T>, currency_b: CurrencyIdOf<T>, window: T::BlockNumber) -> Option<Price> {
+		let observations = Self::price_observations(currency_a, currency_b);
+		let now = <frame_system::Module<T>>::block_number();
+		if window.is_zero() {
+			return observations.last().map(|last| last.price);
+		}
+		let start = now.checked_sub(&window)?;
+		let cumulative_at = |at: T::BlockNumber| -> Option<Price> {
+			observations
+				.iter()
+				.rev()
+				.find(|observation| observation.at <= at)
+				.map(|observation| Self::cumulative_price_at(observation, at))
+		};
+		cumulative_at(now)?
+			.saturating_sub(cumulative_at(start)?)
+			.checked_div(&Price::saturating_from_integer(window.saturated_into::<u128>()))
+	}
+
+	/// Multiply `amount` by `price`, saturating at the numeric bounds.
+	fn price_mul_int(price: Price, amount: BalanceOf<T>) -> BalanceOf<T> {
+		price.saturating_mul_int(amount.saturated_into::<u128>()).saturated_into()
+	}
+
+	/// The most recent supply adjustment of `currency_id`, if any.
+	pub fn latest_supply_adjustment(
+		currency_id: CurrencyIdOf<T>,
+	) -> Option<SupplyAdjustment<T::BlockNumber, BalanceOf<T>>> {
+		Self::supply_adjustments(currency_id).pop()
+	}
+
+	/// Append an adjustment to the history of `currency_id`, dropping the
+	/// oldest entries beyond `MaxSupplyHistory`.
+	fn record_supply_adjustment(
+		currency_id: CurrencyIdOf<T>,
+		direction: SerpDirection,
+		amount: BalanceOf<T>,
+		quote_price: BalanceOf<T>,
+	) {
+		let adjustment = SupplyAdjustment {
+			at: <frame_system::Module<T>>::block_number(),
+			direction,
+			amount,
+			quote_price,
+			total_issuance: Self::total_issuance(currency_id),
+		};
+		let max_history = T::MaxSupplyHistory::get() as usize;
+		SupplyAdjustments::<T>::mutate(currency_id, |history| {
+			history.push(adjustment);
+			if history.len() > max_history {
+				let excess = history.len() - max_history;
+				*history = history.split_off(excess);
+			}
+		});
+	}
+}
+
+/// A `PriceProvider` of the median of the fresh prices fed to the Market
+/// oracle by its feeders.
+pub struct MedianPriceProvider<T>(marker::PhantomData<T>);
+
+impl<T: Config> PriceProvider<CurrencyIdOf<T>> for MedianPriceProvider<T> {
+	fn get_price(currency_id: CurrencyIdOf<T>) -> Option<Price> {
+		Pallet::<T>::median_price(currency_id)
+	}
+}
+
+/// A `PriceProvider` of the time-weighted average price of each currency
+/// over the last `GetWindow` blocks of Market trades, denominated in the
+/// native currency.
+pub struct TwapPriceProvider<T, GetWindow>(marker::PhantomData<(T, GetWindow)>);
+
+impl<T, GetWindow> PriceProvider<CurrencyIdOf<T>> for TwapPriceProvider<T, GetWindow>
+where
+	T: Config,
+	GetWindow: Get<T::BlockNumber>,
+{
+	fn get_price(currency_id: CurrencyIdOf<T>) -> Option<Price> {
+		let native_currency_id = T::GetStp258NativeId::get();
+		if currency_id == native_currency_id {
+			Some(Price::one())
+		} else {
+			Pallet::<T>::twap(currency_id, native_currency_id, GetWindow::get())
+		}
+	}
+}
+
+/// The base units of the settcurrencies, for the `GetBaseUnit` of the
+/// currency layer: the largest base unit of the constituents of a basket,
+/// otherwise the base unit from `GetBaseUnit`.
+pub struct BasketBaseUnits<T, GetBaseUnit>(marker::PhantomData<(T, GetBaseUnit)>);
+
+impl<T, GetBaseUnit> GetByKey<CurrencyIdOf<T>, BalanceOf<T>> for BasketBaseUnits<T, GetBaseUnit>
+where
+	T: Config,
+	GetBaseUnit: GetByKey<CurrencyIdOf<T>, BalanceOf<T>>,
+{
+	fn get(currency_id: &CurrencyIdOf<T>) -> BalanceOf<T> {
+		Pallet::<T>::basket(currency_id)
+			.iter()
+			.map(|(constituent, _)| GetBaseUnit::get(constituent))
+			.max()
+			.unwrap_or_else(|| GetBaseUnit::get(currency_id))
 	}
 }
 
@@ -294,7 +4566,10 @@ impl<T: Config> Stp258Currency<T::AccountId> for Pallet<T> {
 		if amount.is_zero() || from == to {
 			return Ok(());
 		}
+		Self::ensure_not_paused(currency_id)?;
+		Self::ensure_not_settled(currency_id)?;
 		if currency_id == T::GetStp258NativeId::get() {
+			ensure!(!Self::native_transfers_paused(), Error::<T>::NativeTransfersArePaused);
 			T::Stp258Native::transfer(from, to, amount)?;
 		} else {
 			T::Stp258Currency::transfer(currency_id, from, to, amount)?;
@@ -307,6 +4582,8 @@ impl<T: Config> Stp258Currency<T::AccountId> for Pallet<T> {
 		if amount.is_zero() {
 			return Ok(());
 		}
+		Self::ensure_not_paused(currency_id)?;
+		Self::ensure_not_settled(currency_id)?;
 		if currency_id == T::GetStp258NativeId::get() {
 			T::Stp258Native::deposit(who, amount)?;
 		} else {
@@ -320,6 +4597,7 @@ impl<T: Config> Stp258Currency<T::AccountId> for Pallet<T> {
 		if amount.is_zero() {
 			return Ok(());
 		}
+		Self::ensure_not_paused(currency_id)?;
 		if currency_id == T::GetStp258NativeId::get() {
 			T::Stp258Native::withdraw(who, amount)?;
 		} else {
@@ -350,6 +4628,8 @@ impl<T: Config> Stp258CurrencyExtended<T::AccountId> for Pallet<T> {
 	type Amount = AmountOf<T>;
 
 	fn update_balance(currency_id: Self::CurrencyId, who: &T::AccountId, by_amount: Self::Amount) -> DispatchResult {
+		Self::ensure_not_paused(currency_id)?;
+		Self::ensure_not_settled(currency_id)?;
 		if currency_id == T::GetStp258NativeId::get() {
 			T::Stp258Native::update_balance(who, by_amount)?;
 		} else {
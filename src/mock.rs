@@ -4,6 +4,7 @@
 
 use super::*;
 use frame_support::{construct_runtime, parameter_types};
+use frame_system::EnsureRoot;
 use serp_traits::parameter_type_with_key;
 use sp_core::H256;
 use sp_runtime::{
@@ -11,6 +12,7 @@ use sp_runtime::{
 	traits::{AccountIdConversion, IdentityLookup},
 	AccountId32, ModuleId, Perbill,
 };
+use std::{cell::RefCell, collections::BTreeMap};
 
 use crate as market;
 
@@ -73,6 +75,7 @@ parameter_type_with_key! {
 		match currency_id {
 			&SETT => 10_000,
 			&JUSD => 1_000,
+			&JEUR => 100,
 			_ => 0,
 		}
 	};
@@ -107,7 +110,7 @@ impl stp258_serp::Config for Runtime {
 	type CurrencyId = CurrencyId;
 	type WeightInfo = ();
 	type ExistentialDeposits = ExistentialDeposits;
-	type GetBaseUnit = GetBaseUnit;
+	type GetBaseUnit = market::BasketBaseUnits<Runtime, GetBaseUnit>;
 	type AdjustmentFrequency = AdjustmentFrequency;
 	type GetPercent = GetPercent;
 	type GetSerpNativeId = GetSerpNativeId;
@@ -123,11 +126,76 @@ impl stp258_serp::Config for Runtime {
 pub const DNAR: CurrencyId = 1;
 pub const SETT: CurrencyId = 2;
 pub const JUSD: CurrencyId = 3;
+pub const JEUR: CurrencyId = 4;
 
 pub const ADJUSTMENT_FREQUENCY: Blocknumber = 10;
 
+thread_local! {
+	static PRICES: RefCell<BTreeMap<CurrencyId, Price>> = RefCell::new(BTreeMap::new());
+	static USE_MEDIAN_ORACLE: RefCell<bool> = RefCell::new(false);
+}
+
+pub struct MockPriceSource;
+impl MockPriceSource {
+	pub fn set_price(currency_id: CurrencyId, price: Price) {
+		PRICES.with(|prices| {
+			prices.borrow_mut().insert(currency_id, price);
+		});
+	}
+
+	/// Take prices from the Market oracle feeders instead of `set_price`.
+	pub fn use_median_oracle() {
+		USE_MEDIAN_ORACLE.with(|use_median| *use_median.borrow_mut() = true);
+	}
+}
+impl PriceProvider<CurrencyId> for MockPriceSource {
+	fn get_price(currency_id: CurrencyId) -> Option<Price> {
+		if USE_MEDIAN_ORACLE.with(|use_median| *use_median.borrow()) {
+			return MedianPriceProvider::<Runtime>::get_price(currency_id);
+		}
+		PRICES.with(|prices| prices.borrow().get(&currency_id).copied())
+	}
+}
+
 parameter_types! {
 	pub const GetStp258NativeId: CurrencyId = DNAR;
+	pub const MaxSupplyHistory: u32 = 3;
+	pub const AdjustmentPeriod: Blocknumber = ADJUSTMENT_FREQUENCY;
+	pub const MaxAutoSerpsPerBlock: u32 = 2;
+	pub const SettlementReserveAccount: AccountId = SETTLEMENT_RESERVE;
+	pub const SwapBasePool: Balance = 1_000;
+	pub const PoolRecoveryPeriod: Blocknumber = 10;
+	pub const MinSwapSpread: Perbill = Perbill::from_percent(1);
+	pub const MarketModuleId: ModuleId = ModuleId(*b"set/mrkt");
+	pub const PoolSwapFee: Perbill = Perbill::from_percent(1);
+	pub const MaxOrdersPerSide: u32 = 3;
+	pub const MaxPriceObservations: u32 = 10;
+	pub const RootOperatorAccountId: AccountId = ROOT_OPERATOR;
+	pub const MaxFeeders: u32 = 3;
+	pub const MaxPriceAge: Blocknumber = 5;
+	pub const MaxPriceDeviation: Perbill = Perbill::from_percent(20);
+	pub const MinFeeds: u32 = 2;
+	pub const VotePeriod: Blocknumber = 5;
+	pub const MinFeederBond: Balance = 10;
+	pub const UnbondingPeriod: Blocknumber = 10;
+	pub const RewardBand: Perbill = Perbill::from_percent(5);
+	pub const MaxMissedVotes: u32 = 2;
+	pub const MissSlashFraction: Perbill = Perbill::from_percent(50);
+	pub const OracleRewardShare: Perbill = Perbill::from_percent(10);
+	pub const BondLifetime: Option<Blocknumber> = Some(20);
+	pub const MaxBondsPerCurrency: u32 = 3;
+	pub const MinBondAmount: Balance = 1_000;
+	pub const MaxBeneficiaries: u32 = 3;
+	pub const StakingLockId: LockIdentifier = *b"mrktstak";
+	pub const StakerShare: Perbill = Perbill::from_percent(20);
+	pub const AuctionDuration: Blocknumber = 10;
+	pub const AuctionMinIncrement: Perbill = Perbill::from_percent(10);
+	pub const AuctionExtension: Blocknumber = 3;
+	pub const MintRedeemBaseFee: Perbill = Perbill::from_percent(1);
+	pub const MintRedeemFeeSensitivity: Perbill = Perbill::from_percent(50);
+	pub const MaxMintRedeemPerBlock: Perbill = Perbill::from_percent(10);
+	pub const TobinTax: Perbill = Perbill::from_percent(1);
+	pub const MaxBasketConstituents: u32 = 3;
 }
 
 impl stp258_standard::Config for Runtime {
@@ -145,6 +213,48 @@ impl Config for Runtime {
 	type Stp258Currency = Stp258Serp;
 	type Stp258Native = AdaptedStp258Asset;
 	type GetStp258NativeId = GetStp258NativeId;
+	type SerpOrigin = EnsureRoot<AccountId>;
+	type MaxSupplyHistory = MaxSupplyHistory;
+	type Oracle = MockPriceSource;
+	type AdjustmentPeriod = AdjustmentPeriod;
+	type MaxAutoSerpsPerBlock = MaxAutoSerpsPerBlock;
+	type PauseOrigin = EnsureRoot<AccountId>;
+	type SettlementReserveAccount = SettlementReserveAccount;
+	type GetSerperAcc = GetSerperAcc;
+	type SwapBasePool = SwapBasePool;
+	type PoolRecoveryPeriod = PoolRecoveryPeriod;
+	type MinSwapSpread = MinSwapSpread;
+	type ModuleId = MarketModuleId;
+	type PoolSwapFee = PoolSwapFee;
+	type MaxOrdersPerSide = MaxOrdersPerSide;
+	type MaxPriceObservations = MaxPriceObservations;
+	type FeederOrigin = EnsureRoot<AccountId>;
+	type RootOperatorAccountId = RootOperatorAccountId;
+	type MaxFeeders = MaxFeeders;
+	type MaxPriceAge = MaxPriceAge;
+	type MaxPriceDeviation = MaxPriceDeviation;
+	type MinFeeds = MinFeeds;
+	type VotePeriod = VotePeriod;
+	type MinFeederBond = MinFeederBond;
+	type UnbondingPeriod = UnbondingPeriod;
+	type RewardBand = RewardBand;
+	type MaxMissedVotes = MaxMissedVotes;
+	type MissSlashFraction = MissSlashFraction;
+	type OracleRewardShare = OracleRewardShare;
+	type BondLifetime = BondLifetime;
+	type MaxBondsPerCurrency = MaxBondsPerCurrency;
+	type MinBondAmount = MinBondAmount;
+	type MaxBeneficiaries = MaxBeneficiaries;
+	type StakingLockId = StakingLockId;
+	type StakerShare = StakerShare;
+	type AuctionDuration = AuctionDuration;
+	type AuctionMinIncrement = AuctionMinIncrement;
+	type AuctionExtension = AuctionExtension;
+	type MintRedeemBaseFee = MintRedeemBaseFee;
+	type MintRedeemFeeSensitivity = MintRedeemFeeSensitivity;
+	type MaxMintRedeemPerBlock = MaxMintRedeemPerBlock;
+	type TobinTax = TobinTax;
+	type MaxBasketConstituents = MaxBasketConstituents;
 	type WeightInfo = ();
 }
 
@@ -158,7 +268,7 @@ construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Module, Call, Storage, Config, Event<T>},
-		Market: market::{Module, Call, Event<T>},
+		Market: market::{Module, Call, Storage, Event<T>},
 		Stp258Standard: stp258_standard::{Module, Call, Event<T>},
 		Stp258Serp: stp258_serp::{Module, Storage, Event<T>, Config<T>},
 		PalletBalances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
@@ -169,6 +279,8 @@ pub const ALICE: AccountId = AccountId32::new([0u8; 32]);
 pub const BOB: AccountId = AccountId32::new([1u8; 32]);
 pub const SERPER: AccountId = AccountId32::new([3u8; 32]);
 pub const SETTPAY: AccountId = AccountId32::new([4u8; 32]);
+pub const SETTLEMENT_RESERVE: AccountId = AccountId32::new([5u8; 32]);
+pub const ROOT_OPERATOR: AccountId = AccountId32::new([6u8; 32]);
 
 pub struct ExtBuilder {
 	endowed_accounts: Vec<(AccountId, CurrencyId, Balance)>,
@@ -3,9 +3,9 @@
 #![cfg(test)]
 
 use super::*;
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{assert_err, assert_noop, assert_ok};
 use mock::{Event, *};
-use sp_runtime::traits::BadOrigin;
+use sp_runtime::traits::{BadOrigin, One};
 
 #[test]
 fn expand_supply_should_work() {
@@ -243,3 +243,2517 @@ fn call_event_should_work() {
 		});
 }
 
+
+#[test]
+fn expand_supply_call_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			MockPriceSource::set_price(DNAR, Price::saturating_from_integer(4));
+			MockPriceSource::set_price(JUSD, Price::one());
+
+			assert_eq!(Market::total_issuance(JUSD), 400 * 1_000);
+			assert_ok!(Market::expand_supply(Origin::root(), JUSD, 40 * 1_000));
+			assert_eq!(Market::total_issuance(JUSD), 440 * 1_000);
+
+			let serped_up_event = Event::market(crate::Event::SerpedUpSupply(JUSD, 40 * 1_000));
+			assert!(System::events().iter().any(|record| record.event == serped_up_event));
+		});
+}
+
+#[test]
+fn contract_supply_call_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			MockPriceSource::set_price(DNAR, Price::saturating_from_integer(4));
+			MockPriceSource::set_price(JUSD, Price::one());
+
+			assert_ok!(Market::update_balance(Origin::root(), SERPER, JUSD, 1_000 * 1_000));
+			assert_ok!(Market::reserve(JUSD, &SERPER, 1_000 * 1_000));
+			assert_eq!(Market::total_issuance(JUSD), 1_400 * 1_000);
+			assert_ok!(Market::contract_supply(Origin::root(), JUSD, 40 * 1_000));
+			assert_eq!(Market::reserved_balance(JUSD, &SERPER), 960 * 1_000);
+			assert_eq!(Market::total_issuance(JUSD), 1_360 * 1_000);
+
+			let serped_down_event = Event::market(crate::Event::SerpedDownSupply(JUSD, 40 * 1_000));
+			assert!(System::events().iter().any(|record| record.event == serped_down_event));
+		});
+}
+
+#[test]
+fn serp_calls_fail_if_not_serp_origin() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Market::expand_supply(Some(ALICE).into(), JUSD, 40 * 1_000),
+				BadOrigin
+			);
+			assert_noop!(
+				Market::contract_supply(Some(ALICE).into(), JUSD, 40 * 1_000),
+				BadOrigin
+			);
+		});
+}
+
+#[test]
+fn serping_with_unrecognised_native_currency_should_fail() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_noop!(
+				<Market as SerpMarket<AccountId>>::expand_supply(SETT, JUSD, 40 * 1_000, 4_000),
+				Error::<Runtime>::UnrecognisedNativeCurrency
+			);
+			assert_noop!(
+				<Market as SerpMarket<AccountId>>::contract_supply(SETT, JUSD, 40 * 1_000, 4_000),
+				Error::<Runtime>::UnrecognisedNativeCurrency
+			);
+			assert_eq!(Market::total_issuance(JUSD), 400 * 1_000);
+			assert!(System::events().is_empty());
+		});
+}
+
+#[test]
+fn serping_native_currency_should_fail() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_noop!(
+				<Market as SerpMarket<AccountId>>::expand_supply(DNAR, DNAR, 40, 4_000),
+				Error::<Runtime>::NotSerpable
+			);
+			assert_noop!(
+				Market::contract_supply(Origin::root(), DNAR, 40),
+				Error::<Runtime>::NotSerpable
+			);
+			assert_eq!(Market::total_issuance(DNAR), 400);
+			assert!(System::events().is_empty());
+		});
+}
+
+#[test]
+fn serping_zero_amount_should_be_noop() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(<Market as SerpMarket<AccountId>>::expand_supply(DNAR, JUSD, 0, 4_000));
+			assert_ok!(<Market as SerpMarket<AccountId>>::contract_supply(DNAR, JUSD, 0, 4_000));
+			assert_eq!(Market::total_issuance(JUSD), 400 * 1_000);
+			assert!(System::events().is_empty());
+		});
+}
+
+#[test]
+fn serping_should_record_supply_adjustments() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			MockPriceSource::set_price(DNAR, Price::saturating_from_integer(4));
+			MockPriceSource::set_price(JUSD, Price::one());
+			assert_ok!(Market::expand_supply(Origin::root(), JUSD, 40 * 1_000));
+
+			System::set_block_number(2);
+			MockPriceSource::set_price(DNAR, Price::saturating_from_integer(5));
+			assert_ok!(Market::reserve(JUSD, &SERPER, 100 * 1_000));
+			assert_ok!(Market::contract_supply(Origin::root(), JUSD, 20 * 1_000));
+
+			assert_eq!(
+				Market::supply_adjustments(JUSD),
+				vec![
+					SupplyAdjustment {
+						at: 1,
+						direction: SerpDirection::Expansion,
+						amount: 40 * 1_000,
+						quote_price: 4_000,
+						total_issuance: 440 * 1_000,
+					},
+					SupplyAdjustment {
+						at: 2,
+						direction: SerpDirection::Contraction,
+						amount: 20 * 1_000,
+						quote_price: 5_000,
+						total_issuance: 420 * 1_000,
+					},
+				]
+			);
+			assert_eq!(Market::latest_supply_adjustment(JUSD).map(|a| a.at), Some(2));
+			assert!(Market::supply_adjustments(SETT).is_empty());
+		});
+}
+
+#[test]
+fn supply_adjustments_should_keep_only_max_history() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			MockPriceSource::set_price(DNAR, Price::saturating_from_integer(4));
+			MockPriceSource::set_price(JUSD, Price::one());
+
+			for block in 1..=5 {
+				System::set_block_number(block);
+				assert_ok!(Market::expand_supply(Origin::root(), JUSD, 1_000));
+			}
+
+			let history = Market::supply_adjustments(JUSD);
+			assert_eq!(history.len(), 3);
+			assert_eq!(history.iter().map(|a| a.at).collect::<Vec<_>>(), vec![3, 4, 5]);
+			assert_eq!(history[2].total_issuance, 405 * 1_000);
+		});
+}
+
+#[test]
+fn set_peg_target_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_noop!(
+			Market::set_peg_target(Some(ALICE).into(), JUSD, Some(Price::one())),
+			BadOrigin
+		);
+		assert_noop!(
+			Market::set_peg_target(Origin::root(), DNAR, Some(Price::one())),
+			Error::<Runtime>::NotSerpable
+		);
+		assert_noop!(
+			Market::set_peg_target(Origin::root(), JUSD, Some(Price::zero())),
+			Error::<Runtime>::InvalidPegTarget
+		);
+
+		assert_ok!(Market::set_peg_target(Origin::root(), JUSD, Some(Price::one())));
+		assert_eq!(Market::peg_target(JUSD), Some(Price::one()));
+		let peg_event = Event::market(crate::Event::PegTargetUpdated(JUSD, Some(Price::one())));
+		assert!(System::events().iter().any(|record| record.event == peg_event));
+
+		assert_ok!(Market::set_peg_target(Origin::root(), JUSD, None));
+		assert_eq!(Market::peg_target(JUSD), None);
+	});
+}
+
+#[test]
+fn peg_targets_should_be_limited_to_auto_serps_per_block() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Market::set_peg_target(Origin::root(), JUSD, Some(Price::one())));
+		assert_ok!(Market::set_peg_target(Origin::root(), SETT, Some(Price::one())));
+		assert_noop!(
+			Market::set_peg_target(Origin::root(), JEUR, Some(Price::one())),
+			Error::<Runtime>::TooManyPeggedCurrencies
+		);
+		assert_noop!(
+			Market::set_basket(Origin::root(), JEUR, vec![(JUSD, Perbill::one())]),
+			Error::<Runtime>::TooManyPeggedCurrencies
+		);
+		assert_ok!(Market::set_peg_target(Origin::root(), JUSD, Some(Price::saturating_from_integer(2))));
+
+		assert_ok!(Market::set_peg_target(Origin::root(), SETT, None));
+		assert_ok!(Market::set_peg_target(Origin::root(), JEUR, Some(Price::one())));
+	});
+}
+
+#[test]
+fn on_initialize_should_expand_supply_above_peg() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			MockPriceSource::set_price(DNAR, Price::saturating_from_integer(4));
+			MockPriceSource::set_price(JUSD, Price::saturating_from_rational(11, 10));
+			assert_ok!(Market::set_peg_target(Origin::root(), JUSD, Some(Price::one())));
+
+			Market::on_initialize(ADJUSTMENT_FREQUENCY - 1);
+			assert_eq!(Market::total_issuance(JUSD), 400 * 1_000);
+
+			System::set_block_number(ADJUSTMENT_FREQUENCY);
+			Market::on_initialize(ADJUSTMENT_FREQUENCY);
+			assert_eq!(Market::total_issuance(JUSD), 440 * 1_000);
+			assert_eq!(
+				Market::latest_supply_adjustment(JUSD).map(|a| (a.direction, a.quote_price)),
+				Some((SerpDirection::Expansion, 3_636))
+			);
+		});
+}
+
+#[test]
+fn on_initialize_should_contract_supply_below_peg() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			MockPriceSource::set_price(DNAR, Price::saturating_from_integer(4));
+			MockPriceSource::set_price(JUSD, Price::saturating_from_rational(9, 10));
+			assert_ok!(Market::set_peg_target(Origin::root(), JUSD, Some(Price::one())));
+			assert_ok!(Market::reserve(JUSD, &SERPER, 100 * 1_000));
+
+			System::set_block_number(ADJUSTMENT_FREQUENCY);
+			Market::on_initialize(ADJUSTMENT_FREQUENCY);
+			assert_eq!(Market::total_issuance(JUSD), 360 * 1_000);
+			assert_eq!(Market::reserved_balance(JUSD, &SERPER), 60 * 1_000);
+		});
+}
+
+#[test]
+fn on_initialize_should_skip_on_peg_or_without_price() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(ADJUSTMENT_FREQUENCY);
+			assert_ok!(Market::set_peg_target(Origin::root(), JUSD, Some(Price::one())));
+
+			Market::on_initialize(ADJUSTMENT_FREQUENCY);
+			let skipped_event = Event::market(crate::Event::SerpSkipped(JUSD));
+			assert!(System::events().iter().any(|record| record.event == skipped_event));
+
+			System::reset_events();
+			MockPriceSource::set_price(DNAR, Price::saturating_from_integer(4));
+			MockPriceSource::set_price(JUSD, Price::one());
+			Market::on_initialize(2 * ADJUSTMENT_FREQUENCY);
+			assert!(System::events().iter().any(|record| record.event == skipped_event));
+			assert_eq!(Market::total_issuance(JUSD), 400 * 1_000);
+			assert!(Market::supply_adjustments(JUSD).is_empty());
+		});
+}
+
+#[test]
+fn set_supply_change_limit_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let limit = SupplyChangeLimit {
+			max_single_change: Perbill::from_percent(5),
+			max_window_change: Perbill::from_percent(10),
+			window: 10,
+		};
+
+		assert_noop!(
+			Market::set_supply_change_limit(Some(ALICE).into(), JUSD, Some(limit)),
+			BadOrigin
+		);
+		assert_ok!(Market::set_supply_change_limit(Origin::root(), JUSD, Some(limit)));
+		assert_eq!(Market::supply_change_limit(JUSD), Some(limit));
+		let limit_event = Event::market(crate::Event::SupplyChangeLimitUpdated(JUSD, Some(limit)));
+		assert!(System::events().iter().any(|record| record.event == limit_event));
+
+		assert_ok!(Market::set_supply_change_limit(Origin::root(), JUSD, None));
+		assert_eq!(Market::supply_change_limit(JUSD), None);
+
+		assert_noop!(
+			Market::set_supply_change_limit(
+				Origin::root(),
+				JUSD,
+				Some(SupplyChangeLimit { window: 0, ..limit })
+			),
+			Error::<Runtime>::InvalidSupplyChangeLimit
+		);
+	});
+}
+
+#[test]
+fn serping_above_single_change_limit_should_fail() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			MockPriceSource::set_price(DNAR, Price::saturating_from_integer(4));
+			MockPriceSource::set_price(JUSD, Price::one());
+			assert_ok!(Market::set_supply_change_limit(
+				Origin::root(),
+				JUSD,
+				Some(SupplyChangeLimit {
+					max_single_change: Perbill::from_percent(5),
+					max_window_change: Perbill::from_percent(100),
+					window: 10,
+				})
+			));
+
+			assert_err!(
+				Market::expand_supply(Origin::root(), JUSD, 40 * 1_000),
+				Error::<Runtime>::SupplyChangeTooLarge
+			);
+			let exceeded_event = Event::market(crate::Event::SupplyChangeLimitExceeded(JUSD, 40 * 1_000));
+			assert!(System::events().iter().any(|record| record.event == exceeded_event));
+			assert_eq!(Market::total_issuance(JUSD), 400 * 1_000);
+
+			assert_ok!(Market::expand_supply(Origin::root(), JUSD, 20 * 1_000));
+			assert_eq!(Market::total_issuance(JUSD), 420 * 1_000);
+		});
+}
+
+#[test]
+fn serping_above_window_change_limit_should_fail() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			MockPriceSource::set_price(DNAR, Price::saturating_from_integer(4));
+			MockPriceSource::set_price(JUSD, Price::one());
+			assert_ok!(Market::set_supply_change_limit(
+				Origin::root(),
+				JUSD,
+				Some(SupplyChangeLimit {
+					max_single_change: Perbill::from_percent(5),
+					max_window_change: Perbill::from_percent(10),
+					window: 10,
+				})
+			));
+			assert_ok!(Market::reserve(JUSD, &SERPER, 100 * 1_000));
+
+			assert_ok!(Market::expand_supply(Origin::root(), JUSD, 20 * 1_000));
+			System::set_block_number(5);
+			assert_ok!(Market::contract_supply(Origin::root(), JUSD, 20 * 1_000));
+			assert_eq!(Market::supply_change_window(JUSD), vec![(1, 20 * 1_000), (5, 20 * 1_000)]);
+
+			assert_err!(
+				Market::expand_supply(Origin::root(), JUSD, 1_000),
+				Error::<Runtime>::SupplyChangeRateExceeded
+			);
+			let exceeded_event = Event::market(crate::Event::SupplyChangeLimitExceeded(JUSD, 1_000));
+			assert!(System::events().iter().any(|record| record.event == exceeded_event));
+
+			// The change at block 5 is still within the trailing window.
+			System::set_block_number(11);
+			assert_ok!(Market::expand_supply(Origin::root(), JUSD, 1_000));
+			assert_eq!(Market::supply_change_window(JUSD), vec![(5, 20 * 1_000), (11, 1_000)]);
+			assert_err!(
+				Market::expand_supply(Origin::root(), JUSD, 20 * 1_000),
+				Error::<Runtime>::SupplyChangeRateExceeded
+			);
+
+			System::set_block_number(15);
+			assert_ok!(Market::expand_supply(Origin::root(), JUSD, 20 * 1_000));
+			assert_eq!(Market::supply_change_window(JUSD), vec![(11, 1_000), (15, 20 * 1_000)]);
+		});
+}
+
+#[test]
+fn pause_currency_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_noop!(Market::pause_currency(Some(ALICE).into(), SETT), BadOrigin);
+			assert_ok!(Market::pause_currency(Origin::root(), SETT));
+			assert!(Market::is_paused(SETT));
+			let paused_event = Event::market(crate::Event::CurrencyPaused(SETT));
+			assert!(System::events().iter().any(|record| record.event == paused_event));
+
+			assert_noop!(
+				Market::transfer(Some(ALICE).into(), BOB, SETT, 10 * 10_000),
+				Error::<Runtime>::CurrencyIsPaused
+			);
+			assert_noop!(
+				Market::update_balance(Origin::root(), ALICE, SETT, 10 * 10_000),
+				Error::<Runtime>::CurrencyIsPaused
+			);
+			assert_noop!(
+				<Market as Stp258Currency<AccountId>>::deposit(SETT, &ALICE, 10 * 10_000),
+				Error::<Runtime>::CurrencyIsPaused
+			);
+			assert_noop!(
+				<Market as Stp258Currency<AccountId>>::withdraw(SETT, &ALICE, 10 * 10_000),
+				Error::<Runtime>::CurrencyIsPaused
+			);
+			assert_noop!(
+				Market::expand_supply(Origin::root(), SETT, 10 * 10_000),
+				Error::<Runtime>::CurrencyIsPaused
+			);
+			assert_ok!(Market::transfer(Some(ALICE).into(), BOB, JUSD, 10 * 1_000));
+
+			assert_ok!(Market::unpause_currency(Origin::root(), SETT));
+			assert!(!Market::is_paused(SETT));
+			assert_ok!(Market::transfer(Some(ALICE).into(), BOB, SETT, 10 * 10_000));
+			assert_eq!(Market::free_balance(SETT, &BOB), 110 * 10_000);
+		});
+}
+
+#[test]
+fn pause_native_transfers_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Market::set_native_transfers_paused(Some(ALICE).into(), true),
+				BadOrigin
+			);
+			assert_ok!(Market::set_native_transfers_paused(Origin::root(), true));
+			assert!(Market::native_transfers_paused());
+
+			assert_noop!(
+				Market::transfer_native_currency(Some(ALICE).into(), BOB, 50),
+				Error::<Runtime>::NativeTransfersArePaused
+			);
+			assert_noop!(
+				Market::transfer(Some(ALICE).into(), BOB, DNAR, 50),
+				Error::<Runtime>::NativeTransfersArePaused
+			);
+			assert_ok!(Market::transfer(Some(ALICE).into(), BOB, SETT, 10 * 10_000));
+
+			assert_ok!(Market::set_native_transfers_paused(Origin::root(), false));
+			assert_ok!(Market::transfer_native_currency(Some(ALICE).into(), BOB, 50));
+			assert_eq!(Stp258Native::free_balance(&BOB), 150);
+		});
+}
+
+#[test]
+fn global_settle_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Market::set_peg_target(Origin::root(), JUSD, Some(Price::one())));
+
+			assert_noop!(Market::global_settle(Some(ALICE).into(), JUSD, 1), BadOrigin);
+			assert_noop!(
+				Market::global_settle(Origin::root(), DNAR, 1),
+				Error::<Runtime>::NotSerpable
+			);
+			assert_noop!(
+				Market::global_settle(Origin::root(), JUSD, 0),
+				Error::<Runtime>::InvalidSettlementPrice
+			);
+
+			assert_ok!(Market::global_settle(Origin::root(), JUSD, 1));
+			assert_eq!(Market::settlement_price(JUSD), Some(1));
+			assert_eq!(Market::peg_target(JUSD), None);
+			let settled_event = Event::market(crate::Event::GlobalSettled(JUSD, 1));
+			assert!(System::events().iter().any(|record| record.event == settled_event));
+
+			assert_noop!(
+				Market::global_settle(Origin::root(), JUSD, 2),
+				Error::<Runtime>::CurrencySettled
+			);
+			assert_noop!(
+				Market::transfer(Some(ALICE).into(), BOB, JUSD, 10 * 1_000),
+				Error::<Runtime>::CurrencySettled
+			);
+			assert_noop!(
+				Market::update_balance(Origin::root(), ALICE, JUSD, 10 * 1_000),
+				Error::<Runtime>::CurrencySettled
+			);
+			assert_noop!(
+				Market::expand_supply(Origin::root(), JUSD, 10 * 1_000),
+				Error::<Runtime>::CurrencySettled
+			);
+		});
+}
+
+#[test]
+fn redeem_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Market::update_balance(Origin::root(), SETTLEMENT_RESERVE, DNAR, 400));
+
+			assert_noop!(
+				Market::redeem(Some(ALICE).into(), JUSD, 40 * 1_000),
+				Error::<Runtime>::CurrencyNotSettled
+			);
+			assert_ok!(Market::global_settle(Origin::root(), JUSD, 1));
+			assert_noop!(
+				Market::redeem(Some(ALICE).into(), JUSD, 999),
+				Error::<Runtime>::RedeemAmountTooLow
+			);
+
+			assert_ok!(Market::redeem(Some(ALICE).into(), JUSD, 40 * 1_000));
+			assert_eq!(Market::free_balance(JUSD, &ALICE), 60 * 1_000);
+			assert_eq!(Stp258Native::free_balance(&ALICE), 140);
+			assert_eq!(Stp258Native::free_balance(&SETTLEMENT_RESERVE), 360);
+			assert_eq!(Market::settlement_reserve(JUSD), 360);
+			assert_eq!(Market::total_issuance(JUSD), 360 * 1_000);
+			let redeemed_event = Event::market(crate::Event::Redeemed(JUSD, ALICE, 40 * 1_000, 40, 360 * 1_000));
+			assert!(System::events().iter().any(|record| record.event == redeemed_event));
+
+			assert_noop!(
+				Market::redeem(Some(BOB).into(), JUSD, 101 * 1_000),
+				Error::<Runtime>::BalanceTooLow
+			);
+		});
+}
+
+#[test]
+fn redeem_should_be_pro_rata_when_reserve_is_short() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Market::update_balance(Origin::root(), SETTLEMENT_RESERVE, DNAR, 100));
+			assert_ok!(Market::global_settle(Origin::root(), JUSD, 1));
+			assert_eq!(Market::settlement_reserve(JUSD), 100);
+			assert_ok!(Market::global_settle(Origin::root(), SETT, 1));
+			assert_eq!(Market::settlement_reserve(SETT), 0);
+
+			assert_ok!(Market::redeem(Some(ALICE).into(), JUSD, 40 * 1_000));
+			assert_eq!(Stp258Native::free_balance(&ALICE), 110);
+			assert_ok!(Market::redeem(Some(BOB).into(), JUSD, 100 * 1_000));
+			assert_eq!(Stp258Native::free_balance(&BOB), 125);
+			assert_eq!(Market::settlement_reserve(JUSD), 65);
+			assert_noop!(
+				Market::redeem(Some(ALICE).into(), SETT, 10 * 10_000),
+				Error::<Runtime>::RedeemAmountTooLow
+			);
+		});
+}
+
+#[test]
+fn swap_native_for_settcurrency_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			MockPriceSource::set_price(DNAR, Price::saturating_from_integer(4));
+			MockPriceSource::set_price(JUSD, Price::one());
+
+			assert_ok!(Market::swap(Some(ALICE).into(), DNAR, JUSD, 10, 38_462));
+			assert_eq!(Stp258Native::free_balance(&ALICE), 90);
+			assert_eq!(Market::free_balance(JUSD, &ALICE), 138_462);
+			assert_eq!(Market::free_balance(JUSD, &SERPER), 101_538);
+			assert_eq!(Market::total_issuance(DNAR), 390);
+			assert_eq!(Market::swap_pool_delta(JUSD), -40_000);
+
+			let swapped_event = Event::market(crate::Event::Swapped(ALICE, DNAR, JUSD, 10, 38_462, 1_538));
+			assert!(System::events().iter().any(|record| record.event == swapped_event));
+		});
+}
+
+#[test]
+fn swap_settcurrency_for_native_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			MockPriceSource::set_price(DNAR, Price::saturating_from_integer(4));
+			MockPriceSource::set_price(JUSD, Price::one());
+
+			assert_ok!(Market::swap(Some(BOB).into(), JUSD, DNAR, 40 * 1_000, 9));
+			assert_eq!(Market::free_balance(JUSD, &BOB), 60 * 1_000);
+			assert_eq!(Stp258Native::free_balance(&BOB), 109);
+			assert_eq!(Market::total_issuance(JUSD), 360 * 1_000);
+			assert_eq!(Market::swap_pool_delta(JUSD), 40_000);
+		});
+}
+
+#[test]
+fn swap_should_charge_min_spread() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			MockPriceSource::set_price(DNAR, Price::saturating_from_integer(4));
+			MockPriceSource::set_price(JUSD, Price::one());
+
+			assert_ok!(Market::swap(Some(ALICE).into(), DNAR, JUSD, 1, 0));
+			assert_eq!(Market::free_balance(JUSD, &ALICE), 103_960);
+			assert_eq!(Market::free_balance(JUSD, &SERPER), 100_040);
+		});
+}
+
+#[test]
+fn swap_should_fail_for_invalid_input() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Market::swap(Some(ALICE).into(), DNAR, JUSD, 10, 0),
+				Error::<Runtime>::PriceUnavailable
+			);
+
+			MockPriceSource::set_price(DNAR, Price::saturating_from_integer(4));
+			MockPriceSource::set_price(JUSD, Price::one());
+			assert_noop!(
+				Market::swap(Some(ALICE).into(), SETT, JUSD, 10, 0),
+				Error::<Runtime>::InvalidSwapPair
+			);
+			assert_noop!(
+				Market::swap(Some(ALICE).into(), DNAR, DNAR, 10, 0),
+				Error::<Runtime>::InvalidSwapPair
+			);
+			assert_noop!(
+				Market::swap(Some(ALICE).into(), DNAR, JUSD, 10, 38_463),
+				Error::<Runtime>::SlippageExceeded
+			);
+		});
+}
+
+#[test]
+fn swap_should_fail_for_paused_or_settled_settcurrency() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			MockPriceSource::set_price(DNAR, Price::saturating_from_integer(4));
+			MockPriceSource::set_price(JUSD, Price::one());
+
+			assert_ok!(Market::pause_currency(Origin::root(), JUSD));
+			assert_noop!(
+				Market::swap(Some(ALICE).into(), DNAR, JUSD, 10, 0),
+				Error::<Runtime>::CurrencyIsPaused
+			);
+			assert_ok!(Market::unpause_currency(Origin::root(), JUSD));
+
+			assert_ok!(Market::global_settle(Origin::root(), JUSD, 1));
+			assert_noop!(
+				Market::swap(Some(BOB).into(), JUSD, DNAR, 40 * 1_000, 0),
+				Error::<Runtime>::CurrencySettled
+			);
+		});
+}
+
+#[test]
+fn swap_pool_should_recover_every_block() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			MockPriceSource::set_price(DNAR, Price::saturating_from_integer(4));
+			MockPriceSource::set_price(JUSD, Price::one());
+			assert_ok!(Market::swap(Some(ALICE).into(), DNAR, JUSD, 10, 0));
+
+			Market::on_initialize(2);
+			assert_eq!(Market::swap_pool_delta(JUSD), -36_000);
+			Market::on_initialize(3);
+			assert_eq!(Market::swap_pool_delta(JUSD), -32_400);
+		});
+}
+
+#[test]
+fn add_and_remove_liquidity_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			let pool_account = Market::pool_account(JUSD);
+
+			assert_noop!(
+				Market::add_liquidity(Some(ALICE).into(), DNAR, 40, 40),
+				Error::<Runtime>::NotSerpable
+			);
+			assert_ok!(Market::add_liquidity(Some(ALICE).into(), JUSD, 40, 40 * 1_000));
+			assert_eq!(Market::liquidity_pool(JUSD), (40, 40 * 1_000));
+			assert_eq!(Market::liquidity_shares(JUSD, &ALICE), 1_264);
+			assert_eq!(Stp258Native::free_balance(&pool_account), 40);
+			assert_eq!(Market::free_balance(JUSD, &pool_account), 40 * 1_000);
+
+			assert_noop!(
+				Market::add_liquidity(Some(BOB).into(), JUSD, 20, 19 * 1_000),
+				Error::<Runtime>::SlippageExceeded
+			);
+			assert_ok!(Market::add_liquidity(Some(BOB).into(), JUSD, 20, 30 * 1_000));
+			assert_eq!(Market::liquidity_pool(JUSD), (60, 60 * 1_000));
+			assert_eq!(Market::liquidity_shares(JUSD, &BOB), 632);
+			assert_eq!(Market::total_liquidity_shares(JUSD), 1_896);
+			assert_eq!(Market::free_balance(JUSD, &BOB), 80 * 1_000);
+			let added_event = Event::market(crate::Event::LiquidityAdded(BOB, JUSD, 20, 20 * 1_000, 632));
+			assert!(System::events().iter().any(|record| record.event == added_event));
+
+			assert_noop!(
+				Market::remove_liquidity(Some(BOB).into(), JUSD, 633, 0, 0),
+				Error::<Runtime>::InsufficientLiquidityShares
+			);
+			assert_ok!(Market::remove_liquidity(Some(BOB).into(), JUSD, 632, 20, 20 * 1_000));
+			assert_eq!(Market::liquidity_pool(JUSD), (40, 40 * 1_000));
+			assert_eq!(Market::liquidity_shares(JUSD, &BOB), 0);
+			assert_eq!(Market::total_liquidity_shares(JUSD), 1_264);
+			assert_eq!(Stp258Native::free_balance(&BOB), 100);
+			assert_eq!(Market::free_balance(JUSD, &BOB), 100 * 1_000);
+		});
+}
+
+#[test]
+fn pool_swap_exact_in_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Market::swap_exact_in(Some(ALICE).into(), JUSD, DNAR, 10 * 1_000, 0),
+				Error::<Runtime>::InsufficientLiquidity
+			);
+			assert_ok!(Market::add_liquidity(Some(BOB).into(), JUSD, 60, 60 * 1_000));
+			assert_eq!(Market::pool_quote_price(JUSD), Some(1_000));
+
+			assert_noop!(
+				Market::swap_exact_in(Some(ALICE).into(), JUSD, DNAR, 10 * 1_000, 9),
+				Error::<Runtime>::SlippageExceeded
+			);
+			assert_ok!(Market::swap_exact_in(Some(ALICE).into(), JUSD, DNAR, 10 * 1_000, 8));
+			assert_eq!(Market::liquidity_pool(JUSD), (52, 70 * 1_000));
+			assert_eq!(Stp258Native::free_balance(&ALICE), 108);
+			assert_eq!(Market::free_balance(JUSD, &ALICE), 90 * 1_000);
+		});
+}
+
+#[test]
+fn pool_swap_exact_out_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Market::add_liquidity(Some(BOB).into(), JUSD, 60, 60 * 1_000));
+
+			assert_noop!(
+				Market::swap_exact_out(Some(ALICE).into(), DNAR, JUSD, 60 * 1_000, 100),
+				Error::<Runtime>::InsufficientLiquidity
+			);
+			assert_noop!(
+				Market::swap_exact_out(Some(ALICE).into(), DNAR, JUSD, 10 * 1_000, 13),
+				Error::<Runtime>::SlippageExceeded
+			);
+			assert_ok!(Market::swap_exact_out(Some(ALICE).into(), DNAR, JUSD, 10 * 1_000, 14));
+			assert_eq!(Market::liquidity_pool(JUSD), (74, 50 * 1_000));
+			assert_eq!(Stp258Native::free_balance(&ALICE), 86);
+			assert_eq!(Market::free_balance(JUSD, &ALICE), 110 * 1_000);
+		});
+}
+
+#[test]
+fn place_order_should_match_crossing_orders() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(Market::place_order(Some(ALICE).into(), JUSD, OrderSide::Ask, 2, 10 * 1_000, 10));
+			assert_eq!(Market::reserved_balance(JUSD, &ALICE), 10 * 1_000);
+			assert_eq!(Market::order_book(JUSD, OrderSide::Ask), vec![(2, 0)]);
+
+			assert_ok!(Market::place_order(Some(BOB).into(), JUSD, OrderSide::Bid, 3, 4 * 1_000, 10));
+			let filled_event = Event::market(crate::Event::OrderFilled(0, 1, JUSD, 4 * 1_000, 8));
+			assert!(System::events().iter().any(|record| record.event == filled_event));
+
+			assert_eq!(Stp258Native::free_balance(&ALICE), 108);
+			assert_eq!(Market::free_balance(JUSD, &ALICE), 90 * 1_000);
+			assert_eq!(Market::reserved_balance(JUSD, &ALICE), 6 * 1_000);
+			assert_eq!(Stp258Native::free_balance(&BOB), 92);
+			assert_eq!(Stp258Native::reserved_balance(&BOB), 0);
+			assert_eq!(Market::free_balance(JUSD, &BOB), 104 * 1_000);
+
+			assert_eq!(Market::orders(0).map(|order| order.amount), Some(6 * 1_000));
+			assert_eq!(Market::orders(1), None);
+			assert!(Market::order_book(JUSD, OrderSide::Bid).is_empty());
+
+			assert_ok!(Market::place_order(Some(BOB).into(), JUSD, OrderSide::Bid, 2, 10 * 1_000, 10));
+			assert_eq!(Market::orders(0), None);
+			assert!(Market::order_book(JUSD, OrderSide::Ask).is_empty());
+			assert_eq!(Market::order_book(JUSD, OrderSide::Bid), vec![(2, 2)]);
+			assert_eq!(Market::orders(2).map(|order| (order.amount, order.reserved)), Some((4 * 1_000, 8)));
+			assert_eq!(Market::reserved_balance(JUSD, &ALICE), 0);
+			assert_eq!(Stp258Native::reserved_balance(&BOB), 8);
+			assert_eq!(Stp258Native::free_balance(&ALICE), 120);
+		});
+}
+
+#[test]
+fn place_order_should_keep_price_time_priority() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_noop!(
+				Market::place_order(Some(ALICE).into(), JUSD, OrderSide::Ask, 0, 1_000, 10),
+				Error::<Runtime>::InvalidOrder
+			);
+			assert_noop!(
+				Market::place_order(Some(ALICE).into(), JUSD, OrderSide::Ask, 2, 1_000, 1),
+				Error::<Runtime>::InvalidOrder
+			);
+
+			assert_ok!(Market::place_order(Some(ALICE).into(), JUSD, OrderSide::Ask, 3, 1_000, 10));
+			assert_ok!(Market::place_order(Some(ALICE).into(), JUSD, OrderSide::Ask, 2, 1_000, 10));
+			assert_ok!(Market::place_order(Some(BOB).into(), JUSD, OrderSide::Ask, 2, 1_000, 10));
+			assert_eq!(
+				Market::order_book(JUSD, OrderSide::Ask),
+				vec![(2, 1), (2, 2), (3, 0)]
+			);
+			assert_noop!(
+				Market::place_order(Some(BOB).into(), JUSD, OrderSide::Ask, 4, 1_000, 10),
+				Error::<Runtime>::OrderBookFull
+			);
+		});
+}
+
+#[test]
+fn cancel_order_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Market::place_order(Some(BOB).into(), JUSD, OrderSide::Bid, 1, 10 * 1_000, 10));
+			assert_eq!(Stp258Native::reserved_balance(&BOB), 10);
+
+			assert_noop!(Market::cancel_order(Some(BOB).into(), 1), Error::<Runtime>::OrderNotFound);
+			assert_noop!(Market::cancel_order(Some(ALICE).into(), 0), Error::<Runtime>::NotOrderOwner);
+			assert_ok!(Market::cancel_order(Some(BOB).into(), 0));
+			assert_eq!(Stp258Native::reserved_balance(&BOB), 0);
+			assert_eq!(Market::orders(0), None);
+			assert!(Market::order_book(JUSD, OrderSide::Bid).is_empty());
+			let cancelled_event = Event::market(crate::Event::OrderCancelled(0));
+			assert!(System::events().iter().any(|record| record.event == cancelled_event));
+		});
+}
+
+#[test]
+fn orders_should_expire() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Market::place_order(Some(ALICE).into(), JUSD, OrderSide::Ask, 2, 10 * 1_000, 5));
+
+			Market::on_initialize(4);
+			assert!(Market::orders(0).is_some());
+
+			System::set_block_number(5);
+			Market::on_initialize(5);
+			assert_eq!(Market::orders(0), None);
+			assert!(Market::order_book(JUSD, OrderSide::Ask).is_empty());
+			assert_eq!(Market::reserved_balance(JUSD, &ALICE), 0);
+			let expired_event = Event::market(crate::Event::OrderExpired(0));
+			assert!(System::events().iter().any(|record| record.event == expired_event));
+		});
+}
+
+#[test]
+fn trades_should_record_price_observations() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Market::add_liquidity(Some(BOB).into(), JUSD, 60, 60 * 1_000));
+			assert_ok!(Market::swap_exact_in(Some(ALICE).into(), JUSD, DNAR, 10 * 1_000, 8));
+			assert_eq!(
+				Market::price_observations(JUSD, DNAR),
+				vec![PriceObservation {
+					at: 1,
+					cumulative: Price::zero(),
+					price: Price::saturating_from_rational(8, 10),
+					volume: 10 * 1_000,
+				}]
+			);
+
+			System::set_block_number(3);
+			assert_ok!(Market::place_order(Some(ALICE).into(), JUSD, OrderSide::Ask, 2, 1_000, 10));
+			assert_ok!(Market::place_order(Some(BOB).into(), JUSD, OrderSide::Bid, 2, 1_000, 10));
+			assert_eq!(
+				Market::price_observations(JUSD, DNAR).last(),
+				Some(&PriceObservation {
+					at: 3,
+					cumulative: Price::saturating_from_rational(16, 10),
+					price: Price::saturating_from_integer(2),
+					volume: 1_000,
+				})
+			);
+
+			assert_ok!(Market::place_order(Some(ALICE).into(), JUSD, OrderSide::Ask, 4, 3 * 1_000, 10));
+			assert_ok!(Market::place_order(Some(BOB).into(), JUSD, OrderSide::Bid, 4, 3 * 1_000, 10));
+			assert_ok!(Market::place_order(Some(ALICE).into(), JUSD, OrderSide::Ask, 4, 500, 10));
+			assert_ok!(Market::place_order(Some(BOB).into(), JUSD, OrderSide::Bid, 4, 500, 10));
+			assert_eq!(
+				Market::price_observations(JUSD, DNAR).last(),
+				Some(&PriceObservation {
+					at: 3,
+					cumulative: Price::saturating_from_rational(16, 10),
+					price: Price::saturating_from_rational(35, 10),
+					volume: 4 * 1_000,
+				})
+			);
+		});
+}
+
+#[test]
+fn twap_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_eq!(Market::twap(JUSD, DNAR, 0), None);
+			assert_ok!(Market::place_order(Some(ALICE).into(), JUSD, OrderSide::Ask, 2, 4 * 1_000, 10));
+			assert_ok!(Market::place_order(Some(BOB).into(), JUSD, OrderSide::Bid, 2, 4 * 1_000, 10));
+
+			System::set_block_number(5);
+			assert_ok!(Market::place_order(Some(ALICE).into(), JUSD, OrderSide::Ask, 4, 1_000, 10));
+			assert_ok!(Market::place_order(Some(BOB).into(), JUSD, OrderSide::Bid, 4, 1_000, 10));
+
+			System::set_block_number(11);
+			assert_eq!(Market::twap(JUSD, DNAR, 0), Some(Price::saturating_from_integer(4)));
+			assert_eq!(Market::twap(JUSD, DNAR, 10), Some(Price::saturating_from_rational(32, 10)));
+			assert_eq!(Market::twap(JUSD, DNAR, 6), Some(Price::saturating_from_integer(4)));
+			assert_eq!(Market::twap(DNAR, JUSD, 10), Some(Price::saturating_from_rational(10, 32)));
+			assert_eq!(Market::twap(JUSD, DNAR, 11), None);
+			assert_eq!(Market::twap(JUSD, DNAR, 20), None);
+			assert_eq!(Market::twap(SETT, DNAR, 10), None);
+		});
+}
+
+#[test]
+fn serping_without_oracle_price_should_fail() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_noop!(
+				Market::expand_supply(Origin::root(), JUSD, 40 * 1_000),
+				Error::<Runtime>::PriceUnavailable
+			);
+			assert_noop!(
+				<Market as SerpMarket<AccountId>>::expand_supply(DNAR, JUSD, 40 * 1_000, 4_000),
+				Error::<Runtime>::PriceUnavailable
+			);
+		});
+}
+
+#[test]
+fn add_and_remove_feeder_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_noop!(Market::add_feeder(Some(ALICE).into(), ALICE), BadOrigin);
+		assert_ok!(Market::add_feeder(Origin::root(), ALICE));
+		let added_event = Event::market(crate::Event::FeederAdded(ALICE));
+		assert!(System::events().iter().any(|record| record.event == added_event));
+		assert_noop!(
+			Market::add_feeder(Origin::root(), ALICE),
+			Error::<Runtime>::FeederAlreadyAdded
+		);
+		assert_ok!(Market::add_feeder(Origin::root(), BOB));
+		assert_ok!(Market::add_feeder(Origin::root(), SERPER));
+		assert_noop!(
+			Market::add_feeder(Origin::root(), SETTPAY),
+			Error::<Runtime>::TooManyFeeders
+		);
+
+		assert_ok!(Market::remove_feeder(Origin::root(), BOB));
+		assert_eq!(Market::feeders(), vec![ALICE, SERPER]);
+		assert_noop!(
+			Market::remove_feeder(Origin::root(), BOB),
+			Error::<Runtime>::NotFeeder
+		);
+	});
+}
+
+#[test]
+fn feed_price_should_accept_median() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			Market::feed_price(Some(ALICE).into(), JUSD, Price::one()),
+			Error::<Runtime>::NotFeeder
+		);
+		assert_ok!(Market::add_feeder(Origin::root(), ALICE));
+		assert_ok!(Market::add_feeder(Origin::root(), BOB));
+		assert_ok!(Market::add_feeder(Origin::root(), SERPER));
+		assert_noop!(
+			Market::feed_price(Some(ALICE).into(), JUSD, Price::zero()),
+			Error::<Runtime>::InvalidFeedPrice
+		);
+
+		assert_ok!(Market::feed_price(Some(ALICE).into(), JUSD, Price::one()));
+		assert_eq!(Market::median_price(JUSD), None);
+		assert_ok!(Market::feed_price(Some(BOB).into(), JUSD, Price::saturating_from_rational(11, 10)));
+		assert_eq!(Market::median_price(JUSD), Some(Price::saturating_from_rational(105, 100)));
+		assert_ok!(Market::feed_price(Some(SERPER).into(), JUSD, Price::one()));
+		assert_eq!(MedianPriceProvider::<Runtime>::get_price(JUSD), Some(Price::one()));
+
+		assert_noop!(
+			Market::feed_price(Some(ALICE).into(), JUSD, Price::saturating_from_rational(15, 10)),
+			Error::<Runtime>::PriceDeviationTooLarge
+		);
+
+		assert_ok!(Market::feed_price(Origin::root(), JUSD, Price::saturating_from_rational(9, 10)));
+		assert_eq!(
+			Market::raw_price(JUSD, ROOT_OPERATOR),
+			Some((Price::saturating_from_rational(9, 10), 1))
+		);
+		assert_eq!(Market::median_price(JUSD), Some(Price::one()));
+		let fed_event = Event::market(crate::Event::PriceFed(
+			ROOT_OPERATOR,
+			JUSD,
+			Price::saturating_from_rational(9, 10),
+		));
+		assert!(System::events().iter().any(|record| record.event == fed_event));
+	});
+}
+
+#[test]
+fn stale_prices_should_be_ignored() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Market::add_feeder(Origin::root(), ALICE));
+		assert_ok!(Market::add_feeder(Origin::root(), BOB));
+
+		System::set_block_number(1);
+		assert_ok!(Market::feed_price(Some(ALICE).into(), JUSD, Price::one()));
+		System::set_block_number(3);
+		assert_ok!(Market::feed_price(Some(BOB).into(), JUSD, Price::saturating_from_rational(11, 10)));
+		assert_eq!(Market::median_price(JUSD), Some(Price::saturating_from_rational(105, 100)));
+
+		System::set_block_number(8);
+		assert_eq!(Market::median_price(JUSD), Some(Price::saturating_from_rational(105, 100)));
+		System::set_block_number(9);
+		assert_eq!(MedianPriceProvider::<Runtime>::get_price(JUSD), None);
+
+		assert_ok!(Market::feed_price(Some(ALICE).into(), JUSD, Price::saturating_from_rational(15, 10)));
+		assert_eq!(Market::median_price(JUSD), None);
+		assert_ok!(Market::feed_price(Some(BOB).into(), JUSD, Price::saturating_from_rational(14, 10)));
+		assert_eq!(Market::median_price(JUSD), Some(Price::saturating_from_rational(145, 100)));
+	});
+}
+
+#[test]
+fn on_initialize_should_serp_through_median_oracle() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			MockPriceSource::use_median_oracle();
+			assert_ok!(Market::add_feeder(Origin::root(), ALICE));
+			assert_ok!(Market::add_feeder(Origin::root(), BOB));
+			assert_ok!(Market::set_peg_target(Origin::root(), JUSD, Some(Price::one())));
+
+			System::set_block_number(ADJUSTMENT_FREQUENCY);
+			assert_ok!(Market::feed_price(Some(ALICE).into(), DNAR, Price::saturating_from_integer(4)));
+			assert_ok!(Market::feed_price(Some(ALICE).into(), JUSD, Price::saturating_from_rational(11, 10)));
+			Market::on_initialize(ADJUSTMENT_FREQUENCY);
+			assert_eq!(Market::total_issuance(JUSD), 400 * 1_000);
+			let skipped_event = Event::market(crate::Event::SerpSkipped(JUSD));
+			assert!(System::events().iter().any(|record| record.event == skipped_event));
+
+			System::set_block_number(2 * ADJUSTMENT_FREQUENCY);
+			for feeder in &[ALICE, BOB] {
+				assert_ok!(Market::feed_price(Some(*feeder).into(), DNAR, Price::saturating_from_integer(4)));
+				assert_ok!(Market::feed_price(
+					Some(*feeder).into(),
+					JUSD,
+					Price::saturating_from_rational(11, 10)
+				));
+			}
+			Market::on_initialize(2 * ADJUSTMENT_FREQUENCY);
+			assert_eq!(Market::total_issuance(JUSD), 440 * 1_000);
+			assert_eq!(
+				Market::latest_supply_adjustment(JUSD).map(|a| (a.direction, a.quote_price)),
+				Some((SerpDirection::Expansion, 3_636))
+			);
+		});
+}
+
+fn price_vote_hash(salt: u64, price: Price, who: &AccountId) -> <Runtime as frame_system::Config>::Hash {
+	<Runtime as frame_system::Config>::Hashing::hash_of(&(salt, price, who))
+}
+
+#[test]
+fn bond_and_unbond_feeder_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_noop!(Market::bond_feeder(Some(ALICE).into(), 20), Error::<Runtime>::NotFeeder);
+			assert_ok!(Market::add_feeder(Origin::root(), ALICE));
+			assert_ok!(Market::bond_feeder(Some(ALICE).into(), 20));
+			assert_eq!(Market::feeder_bond(&ALICE), 20);
+			assert_eq!(Stp258Native::reserved_balance(&ALICE), 20);
+			let bonded_event = Event::market(crate::Event::FeederBonded(ALICE, 20));
+			assert!(System::events().iter().any(|record| record.event == bonded_event));
+
+			assert_noop!(
+				Market::unbond_feeder(Some(ALICE).into(), 30),
+				Error::<Runtime>::InsufficientFeederBond
+			);
+			assert_ok!(Market::unbond_feeder(Some(ALICE).into(), 5));
+			assert_eq!(Market::feeder_bond(&ALICE), 15);
+			assert_eq!(Market::feeder_unbonding(&ALICE), Some((5, 11)));
+			assert_eq!(Stp258Native::reserved_balance(&ALICE), 20);
+
+			System::set_block_number(6);
+			assert_ok!(Market::unbond_feeder(Some(ALICE).into(), 5));
+			assert_eq!(Market::feeder_unbonding(&ALICE), Some((10, 16)));
+			System::set_block_number(15);
+			assert_noop!(
+				Market::withdraw_feeder_bond(Some(ALICE).into()),
+				Error::<Runtime>::FeederBondUnbonding
+			);
+			System::set_block_number(16);
+			assert_ok!(Market::withdraw_feeder_bond(Some(ALICE).into()));
+			assert_eq!(Market::feeder_unbonding(&ALICE), None);
+			assert_eq!(Stp258Native::reserved_balance(&ALICE), 10);
+			assert_eq!(Stp258Native::free_balance(&ALICE), 90);
+			let withdrawn_event = Event::market(crate::Event::FeederBondWithdrawn(ALICE, 10));
+			assert!(System::events().iter().any(|record| record.event == withdrawn_event));
+			assert_noop!(
+				Market::withdraw_feeder_bond(Some(ALICE).into()),
+				Error::<Runtime>::InsufficientFeederBond
+			);
+		});
+}
+
+#[test]
+fn commit_and_reveal_price_vote_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Market::add_feeder(Origin::root(), ALICE));
+			assert_noop!(
+				Market::commit_price_vote(Some(ALICE).into(), JUSD, price_vote_hash(1, Price::one(), &ALICE)),
+				Error::<Runtime>::InsufficientFeederBond
+			);
+			assert_ok!(Market::bond_feeder(Some(ALICE).into(), 10));
+			assert_noop!(
+				Market::commit_price_vote(Some(ALICE).into(), JUSD, price_vote_hash(1, Price::one(), &ALICE)),
+				Error::<Runtime>::NotPegged
+			);
+			assert_ok!(Market::set_peg_target(Origin::root(), JUSD, Some(Price::one())));
+			assert_ok!(Market::commit_price_vote(
+				Some(ALICE).into(),
+				JUSD,
+				price_vote_hash(1, Price::one(), &ALICE)
+			));
+			assert_noop!(
+				Market::reveal_price_vote(Some(ALICE).into(), JUSD, Price::one(), 1),
+				Error::<Runtime>::NoPriceVoteCommit
+			);
+
+			System::set_block_number(6);
+			assert_noop!(
+				Market::reveal_price_vote(Some(ALICE).into(), JUSD, Price::one(), 2),
+				Error::<Runtime>::PriceVoteMismatch
+			);
+			assert_ok!(Market::reveal_price_vote(Some(ALICE).into(), JUSD, Price::one(), 1));
+			assert_eq!(Market::price_vote(JUSD, &ALICE), Some(Price::one()));
+			assert_eq!(Market::price_vote_commit(JUSD, &ALICE), None);
+		});
+}
+
+#[test]
+fn price_votes_should_be_tallied_rewarded_and_slashed() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			MockPriceSource::set_price(DNAR, Price::saturating_from_integer(4));
+			MockPriceSource::set_price(JUSD, Price::one());
+			assert_ok!(Market::set_peg_target(Origin::root(), JUSD, Some(Price::one())));
+			assert_ok!(Market::expand_supply(Origin::root(), JUSD, 10 * 1_000));
+			let pool_account = Market::oracle_reward_pool_account();
+			assert_eq!(Market::free_balance(JUSD, &pool_account), 1_000);
+			assert_eq!(Market::total_issuance(JUSD), 410 * 1_000);
+
+			let votes = [
+				(ALICE, 20, Price::one(), 1),
+				(BOB, 10, Price::saturating_from_rational(102, 100), 2),
+				(SERPER, 10, Price::saturating_from_rational(15, 10), 3),
+			];
+			for (who, bond, price, salt) in votes.iter() {
+				assert_ok!(Market::add_feeder(Origin::root(), who.clone()));
+				assert_ok!(Market::bond_feeder(Some(who.clone()).into(), *bond));
+				assert_ok!(Market::commit_price_vote(
+					Some(who.clone()).into(),
+					JUSD,
+					price_vote_hash(*salt, *price, who)
+				));
+			}
+			System::set_block_number(6);
+			for (who, _, price, salt) in votes.iter() {
+				assert_ok!(Market::reveal_price_vote(Some(who.clone()).into(), JUSD, *price, *salt));
+			}
+
+			System::set_block_number(10);
+			Market::on_initialize(10);
+			assert_eq!(Market::median_price(JUSD), Some(Price::one()));
+			let tallied_event = Event::market(crate::Event::PriceVoteTallied(JUSD, Price::one()));
+			assert!(System::events().iter().any(|record| record.event == tallied_event));
+			assert_eq!(Market::free_balance(JUSD, &ALICE), 100 * 1_000 + 666);
+			assert_eq!(Market::free_balance(JUSD, &BOB), 100 * 1_000 + 333);
+			assert_eq!(Market::missed_votes(&ALICE), 0);
+			assert_eq!(Market::missed_votes(&SERPER), 1);
+			assert_eq!(Market::price_vote(JUSD, &ALICE), None);
+
+			assert_ok!(Market::feed_price(Some(ALICE).into(), JUSD, Price::saturating_from_rational(11, 10)));
+			assert_ok!(Market::feed_price(Some(BOB).into(), JUSD, Price::saturating_from_rational(11, 10)));
+			assert_eq!(Market::accepted_price(JUSD), Some((Price::saturating_from_rational(11, 10), 10)));
+			assert_eq!(Market::median_price(JUSD), Some(Price::one()));
+
+			System::set_block_number(11);
+			assert_ok!(Market::unbond_feeder(Some(SERPER).into(), 10));
+			for (who, _, price, salt) in votes.iter().take(2) {
+				assert_ok!(Market::commit_price_vote(
+					Some(who.clone()).into(),
+					JUSD,
+					price_vote_hash(*salt, *price, who)
+				));
+			}
+			System::set_block_number(16);
+			for (who, _, price, salt) in votes.iter().take(2) {
+				assert_ok!(Market::reveal_price_vote(Some(who.clone()).into(), JUSD, *price, *salt));
+			}
+			System::set_block_number(20);
+			Market::on_initialize(20);
+			assert_eq!(Market::missed_votes(&SERPER), 0);
+			assert_eq!(Market::feeder_bond(&SERPER), 0);
+			assert_eq!(Market::feeder_unbonding(&SERPER), Some((5, 21)));
+			assert_eq!(Stp258Native::reserved_balance(&SERPER), 5);
+			let slashed_event = Event::market(crate::Event::FeederSlashed(SERPER, 5));
+			assert!(System::events().iter().any(|record| record.event == slashed_event));
+
+			// Once no longer pegged, the currency is no longer under vote.
+			assert_ok!(Market::set_peg_target(Origin::root(), JUSD, None));
+			System::set_block_number(30);
+			Market::on_initialize(30);
+			assert_eq!(Market::voted_price(JUSD), None);
+			assert_eq!(Market::missed_votes(&ALICE), 0);
+		});
+}
+
+fn pid_gains() -> PidGains {
+	PidGains {
+		proportional: FixedI128::saturating_from_rational(1, 2),
+		integral: FixedI128::saturating_from_rational(2, 10),
+		derivative: FixedI128::saturating_from_rational(1, 10),
+		integral_limit: FixedI128::saturating_from_rational(15, 100),
+	}
+}
+
+#[test]
+fn set_pid_gains_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_noop!(
+			Market::set_pid_gains(Some(ALICE).into(), JUSD, Some(pid_gains())),
+			BadOrigin
+		);
+		assert_noop!(
+			Market::set_pid_gains(
+				Origin::root(),
+				JUSD,
+				Some(PidGains {
+					integral: FixedI128::saturating_from_integer(-1),
+					..pid_gains()
+				})
+			),
+			Error::<Runtime>::InvalidPidGains
+		);
+
+		assert_ok!(Market::set_pid_gains(Origin::root(), JUSD, Some(pid_gains())));
+		assert_eq!(Market::pid_gains(JUSD), Some(pid_gains()));
+		let gains_event = Event::market(crate::Event::PidGainsUpdated(JUSD, Some(pid_gains())));
+		assert!(System::events().iter().any(|record| record.event == gains_event));
+
+		assert_ok!(Market::set_pid_gains(Origin::root(), JUSD, None));
+		assert_eq!(Market::pid_gains(JUSD), None);
+	});
+}
+
+#[test]
+fn on_initialize_should_serp_with_pid_controller() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			MockPriceSource::set_price(DNAR, Price::saturating_from_integer(4));
+			MockPriceSource::set_price(JUSD, Price::saturating_from_rational(11, 10));
+			assert_ok!(Market::set_peg_target(Origin::root(), JUSD, Some(Price::one())));
+			assert_ok!(Market::set_pid_gains(Origin::root(), JUSD, Some(pid_gains())));
+
+			assert_ok!(Market::pause_currency(Origin::root(), JUSD));
+			System::set_block_number(ADJUSTMENT_FREQUENCY);
+			Market::on_initialize(ADJUSTMENT_FREQUENCY);
+			assert_eq!(Market::total_issuance(JUSD), 400 * 1_000);
+			assert_eq!(Market::pid_state(JUSD), None);
+			assert_ok!(Market::unpause_currency(Origin::root(), JUSD));
+
+			System::set_block_number(2 * ADJUSTMENT_FREQUENCY);
+			Market::on_initialize(2 * ADJUSTMENT_FREQUENCY);
+			assert_eq!(Market::total_issuance(JUSD), 428 * 1_000);
+			assert_eq!(
+				Market::pid_state(JUSD),
+				Some(PidState {
+					integral: FixedI128::saturating_from_rational(1, 10),
+					last_error: FixedI128::saturating_from_rational(1, 10),
+				})
+			);
+
+			System::set_block_number(3 * ADJUSTMENT_FREQUENCY);
+			Market::on_initialize(3 * ADJUSTMENT_FREQUENCY);
+			assert_eq!(Market::total_issuance(JUSD), 462_240);
+			assert_eq!(
+				Market::pid_state(JUSD).map(|state| state.integral),
+				Some(FixedI128::saturating_from_rational(15, 100))
+			);
+
+			assert_ok!(Market::set_pid_gains(Origin::root(), JUSD, Some(pid_gains())));
+			assert_eq!(Market::pid_state(JUSD), None);
+		});
+}
+
+#[test]
+fn set_peg_band_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_noop!(
+			Market::set_peg_band(Some(ALICE).into(), JUSD, Some(Perbill::from_perthousand(5))),
+			BadOrigin
+		);
+		assert_noop!(
+			Market::set_peg_band(Origin::root(), DNAR, Some(Perbill::from_perthousand(5))),
+			Error::<Runtime>::NotSerpable
+		);
+		assert_ok!(Market::set_peg_band(Origin::root(), JUSD, Some(Perbill::from_perthousand(5))));
+		assert_eq!(Market::peg_band(JUSD), Some(Perbill::from_perthousand(5)));
+		let band_event = Event::market(crate::Event::PegBandUpdated(JUSD, Some(Perbill::from_perthousand(5))));
+		assert!(System::events().iter().any(|record| record.event == band_event));
+
+		assert_ok!(Market::set_peg_band(Origin::root(), JUSD, None));
+		assert_eq!(Market::peg_band(JUSD), None);
+	});
+}
+
+#[test]
+fn serping_within_peg_band_should_be_noop() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			MockPriceSource::set_price(DNAR, Price::saturating_from_integer(4));
+			MockPriceSource::set_price(JUSD, Price::saturating_from_rational(1004, 1000));
+			assert_ok!(Market::set_peg_target(Origin::root(), JUSD, Some(Price::one())));
+			assert_ok!(Market::set_peg_band(Origin::root(), JUSD, Some(Perbill::from_perthousand(5))));
+			assert!(Market::is_within_peg_band(JUSD));
+
+			assert_ok!(<Market as SerpMarket<AccountId>>::expand_supply(DNAR, JUSD, 10 * 1_000, 4_000));
+			assert_eq!(Market::total_issuance(JUSD), 400 * 1_000);
+			let within_band_event = Event::market(crate::Event::SerpWithinBand(JUSD));
+			assert!(System::events().iter().any(|record| record.event == within_band_event));
+
+			System::reset_events();
+			System::set_block_number(ADJUSTMENT_FREQUENCY);
+			Market::on_initialize(ADJUSTMENT_FREQUENCY);
+			assert_eq!(Market::total_issuance(JUSD), 400 * 1_000);
+			assert!(System::events().iter().any(|record| record.event == within_band_event));
+
+			assert_ok!(Market::pause_currency(Origin::root(), JUSD));
+			assert_noop!(
+				<Market as SerpMarket<AccountId>>::contract_supply(DNAR, JUSD, 10 * 1_000, 4_000),
+				Error::<Runtime>::CurrencyIsPaused
+			);
+			assert_ok!(Market::unpause_currency(Origin::root(), JUSD));
+
+			assert_ok!(Market::expand_supply(Origin::root(), JUSD, 10 * 1_000));
+			assert_eq!(Market::total_issuance(JUSD), 410 * 1_000);
+
+			MockPriceSource::set_price(JUSD, Price::saturating_from_rational(11, 10));
+			assert!(!Market::is_within_peg_band(JUSD));
+			assert_ok!(<Market as SerpMarket<AccountId>>::expand_supply(DNAR, JUSD, 10 * 1_000, 4_000));
+			assert_eq!(Market::total_issuance(JUSD), 420 * 1_000);
+		});
+}
+
+#[test]
+fn buy_bonds_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			MockPriceSource::set_price(DNAR, Price::saturating_from_integer(4));
+			MockPriceSource::set_price(JUSD, Price::saturating_from_rational(8, 10));
+			assert_noop!(
+				Market::buy_bonds(Some(ALICE).into(), JUSD, 8 * 1_000),
+				Error::<Runtime>::NotBelowPeg
+			);
+			assert_ok!(Market::set_peg_target(Origin::root(), JUSD, Some(Price::one())));
+			assert_noop!(
+				Market::buy_bonds(Some(ALICE).into(), JUSD, 0),
+				Error::<Runtime>::InvalidBondAmount
+			);
+			assert_noop!(
+				Market::buy_bonds(Some(ALICE).into(), JUSD, 999),
+				Error::<Runtime>::InvalidBondAmount
+			);
+
+			assert_ok!(Market::buy_bonds(Some(ALICE).into(), JUSD, 8 * 1_000));
+			assert_ok!(Market::buy_bonds(Some(BOB).into(), JUSD, 4 * 1_000));
+			assert_eq!(Market::total_issuance(JUSD), 388 * 1_000);
+			assert_eq!(
+				Market::latest_supply_adjustment(JUSD).map(|a| (a.direction, a.quote_price)),
+				Some((SerpDirection::Contraction, 5_000))
+			);
+			assert_eq!(Market::free_balance(JUSD, &ALICE), 92 * 1_000);
+			assert_eq!(
+				Market::bonds(0),
+				Some(Bond {
+					owner: ALICE,
+					currency_id: JUSD,
+					amount: 10 * 1_000,
+					expires_at: Some(21),
+				})
+			);
+			assert_eq!(Market::bond_queue(JUSD), vec![0, 1]);
+			assert_eq!(Market::bond_queue_position(1), Some((1, 10 * 1_000)));
+			let bought_event = Event::market(crate::Event::BondsBought(1, BOB, JUSD, 4 * 1_000, 5 * 1_000));
+			assert!(System::events().iter().any(|record| record.event == bought_event));
+
+			assert_ok!(Market::buy_bonds(Some(BOB).into(), JUSD, 1_000));
+			assert_noop!(
+				Market::buy_bonds(Some(BOB).into(), JUSD, 1_000),
+				Error::<Runtime>::BondQueueFull
+			);
+
+			MockPriceSource::set_price(JUSD, Price::one());
+			assert_noop!(
+				Market::buy_bonds(Some(ALICE).into(), JUSD, 1_000),
+				Error::<Runtime>::NotBelowPeg
+			);
+
+			System::set_block_number(22);
+			MockPriceSource::set_price(JUSD, Price::saturating_from_rational(8, 10));
+			assert_ok!(Market::buy_bonds(Some(BOB).into(), JUSD, 1_000));
+			assert_eq!(Market::bond_queue(JUSD), vec![3]);
+			assert_eq!(Market::bonds(0), None);
+			let expired_event = Event::market(crate::Event::BondExpired(0));
+			assert!(System::events().iter().any(|record| record.event == expired_event));
+		});
+}
+
+#[test]
+fn contract_supply_should_offer_bonds_in_bonds_mode() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			MockPriceSource::set_price(DNAR, Price::saturating_from_integer(4));
+			MockPriceSource::set_price(JUSD, Price::saturating_from_rational(8, 10));
+			assert_ok!(Market::set_peg_target(Origin::root(), JUSD, Some(Price::one())));
+			assert_ok!(Market::set_serp_mode(Origin::root(), JUSD, SerpMode::Bonds));
+
+			assert_ok!(Market::contract_supply(Origin::root(), JUSD, 10 * 1_000));
+			assert_eq!(Market::total_issuance(JUSD), 400 * 1_000);
+			assert_eq!(Market::free_balance(JUSD, &SERPER), 100 * 1_000);
+			assert_eq!(Market::bond_offer(JUSD), 10 * 1_000);
+			assert_eq!(Market::latest_supply_adjustment(JUSD), None);
+			let offered_event = Event::market(crate::Event::BondsOffered(JUSD, 10 * 1_000));
+			assert!(System::events().iter().any(|record| record.event == offered_event));
+
+			assert_ok!(Market::buy_bonds(Some(ALICE).into(), JUSD, 8 * 1_000));
+			assert_eq!(Market::bond_offer(JUSD), 2 * 1_000);
+			assert_noop!(
+				Market::buy_bonds(Some(BOB).into(), JUSD, 4 * 1_000),
+				Error::<Runtime>::BondOfferExceeded
+			);
+			assert_ok!(Market::buy_bonds(Some(BOB).into(), JUSD, 2 * 1_000));
+			assert_eq!(Market::bond_offer(JUSD), 0);
+			assert_eq!(Market::total_issuance(JUSD), 390 * 1_000);
+			assert_eq!(
+				Market::latest_supply_adjustment(JUSD).map(|a| (a.direction, a.amount)),
+				Some((SerpDirection::Contraction, 2 * 1_000))
+			);
+
+			assert_ok!(Market::contract_supply(Origin::root(), JUSD, 1_000));
+			assert_ok!(Market::set_serp_mode(Origin::root(), JUSD, SerpMode::Direct));
+			assert_eq!(Market::bond_offer(JUSD), 0);
+		});
+}
+
+#[test]
+fn expand_supply_should_redeem_bonds_first() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			MockPriceSource::set_price(DNAR, Price::saturating_from_integer(4));
+			MockPriceSource::set_price(JUSD, Price::saturating_from_rational(8, 10));
+			assert_ok!(Market::set_peg_target(Origin::root(), JUSD, Some(Price::one())));
+			assert_ok!(Market::buy_bonds(Some(ALICE).into(), JUSD, 8 * 1_000));
+			assert_ok!(Market::buy_bonds(Some(BOB).into(), JUSD, 4 * 1_000));
+
+			MockPriceSource::set_price(JUSD, Price::saturating_from_rational(11, 10));
+			assert_ok!(Market::expand_supply(Origin::root(), JUSD, 12 * 1_000));
+			assert_eq!(Market::total_issuance(JUSD), 400 * 1_000);
+			assert_eq!(Market::free_balance(JUSD, &ALICE), 102 * 1_000);
+			assert_eq!(Market::free_balance(JUSD, &BOB), 98 * 1_000);
+			assert_eq!(Market::free_balance(JUSD, &Market::oracle_reward_pool_account()), 0);
+			assert_eq!(Market::bonds(0), None);
+			assert_eq!(Market::bonds(1).map(|bond| bond.amount), Some(3 * 1_000));
+			assert_eq!(Market::bond_queue(JUSD), vec![1]);
+			assert_eq!(Market::bond_queue_position(1), Some((0, 0)));
+			let redeemed_event = Event::market(crate::Event::BondRedeemed(1, BOB, JUSD, 2 * 1_000));
+			assert!(System::events().iter().any(|record| record.event == redeemed_event));
+
+			System::set_block_number(22);
+			assert_ok!(Market::expand_supply(Origin::root(), JUSD, 1_000));
+			assert_eq!(Market::bonds(1), None);
+			assert!(Market::bond_queue(JUSD).is_empty());
+			assert_eq!(Market::free_balance(JUSD, &BOB), 98 * 1_000);
+			assert_eq!(Market::free_balance(JUSD, &Market::oracle_reward_pool_account()), 100);
+			let expired_event = Event::market(crate::Event::BondExpired(1));
+			assert!(System::events().iter().any(|record| record.event == expired_event));
+		});
+}
+
+#[test]
+fn set_seigniorage_distribution_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let distribution = vec![(ALICE, Perbill::from_percent(50)), (BOB, Perbill::from_percent(30))];
+
+		assert_noop!(
+			Market::set_seigniorage_distribution(Some(ALICE).into(), JUSD, distribution.clone()),
+			BadOrigin
+		);
+		assert_noop!(
+			Market::set_seigniorage_distribution(
+				Origin::root(),
+				JUSD,
+				vec![(ALICE, Perbill::from_percent(60)), (BOB, Perbill::from_percent(50))]
+			),
+			Error::<Runtime>::InvalidSeigniorageDistribution
+		);
+		assert_noop!(
+			Market::set_seigniorage_distribution(
+				Origin::root(),
+				JUSD,
+				vec![(ALICE, Perbill::from_percent(10)); 4]
+			),
+			Error::<Runtime>::InvalidSeigniorageDistribution
+		);
+
+		assert_ok!(Market::set_seigniorage_distribution(Origin::root(), JUSD, distribution.clone()));
+		assert_eq!(Market::seigniorage_distribution(JUSD), distribution);
+		let updated_event = Event::market(crate::Event::SeigniorageDistributionUpdated(JUSD, distribution));
+		assert!(System::events().iter().any(|record| record.event == updated_event));
+
+		assert_ok!(Market::set_seigniorage_distribution(Origin::root(), JUSD, vec![]));
+		assert!(Market::seigniorage_distribution(JUSD).is_empty());
+	});
+}
+
+#[test]
+fn expand_supply_should_distribute_seigniorage() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			MockPriceSource::set_price(DNAR, Price::saturating_from_integer(4));
+			MockPriceSource::set_price(JUSD, Price::one());
+			assert_ok!(Market::set_seigniorage_distribution(
+				Origin::root(),
+				JUSD,
+				vec![(ALICE, Perbill::from_percent(50)), (BOB, Perbill::from_percent(30))]
+			));
+
+			assert_ok!(Market::expand_supply(Origin::root(), JUSD, 10 * 1_000));
+			assert_eq!(Market::total_issuance(JUSD), 410 * 1_000);
+			assert_eq!(Market::free_balance(JUSD, &ALICE), 104_500);
+			assert_eq!(Market::free_balance(JUSD, &BOB), 102_700);
+			assert_eq!(Market::free_balance(JUSD, &Market::oracle_reward_pool_account()), 1_000);
+			let distributed_event = Event::market(crate::Event::SeigniorageDistributed(
+				JUSD,
+				vec![(ALICE, 4_500), (BOB, 2_700)],
+			));
+			assert!(System::events().iter().any(|record| record.event == distributed_event));
+		});
+}
+
+#[test]
+fn stake_and_unstake_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_noop!(Market::stake(Some(ALICE).into(), 0), Error::<Runtime>::InvalidStakeAmount);
+			assert_noop!(Market::stake(Some(ALICE).into(), 101), Error::<Runtime>::BalanceTooLow);
+			assert_ok!(Market::stake(Some(ALICE).into(), 40));
+			assert_ok!(Market::stake(Some(ALICE).into(), 20));
+			assert_eq!(Market::stakes(&ALICE), 60);
+			assert_eq!(Market::total_staked(), 60);
+			let staked_event = Event::market(crate::Event::Staked(ALICE, 20));
+			assert!(System::events().iter().any(|record| record.event == staked_event));
+			assert!(Market::transfer_native_currency(Some(ALICE).into(), BOB, 50).is_err());
+
+			assert_noop!(
+				Market::unstake(Some(ALICE).into(), 61),
+				Error::<Runtime>::InsufficientStake
+			);
+			assert_ok!(Market::unstake(Some(ALICE).into(), 60));
+			assert_eq!(Market::stakes(&ALICE), 0);
+			assert_eq!(Market::total_staked(), 0);
+			assert_ok!(Market::transfer_native_currency(Some(ALICE).into(), BOB, 50));
+		});
+}
+
+#[test]
+fn stakers_should_earn_seigniorage() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			MockPriceSource::set_price(DNAR, Price::saturating_from_integer(4));
+			MockPriceSource::set_price(JUSD, Price::one());
+			assert_ok!(Market::stake(Some(ALICE).into(), 40));
+			assert_ok!(Market::stake(Some(BOB).into(), 10));
+
+			assert_ok!(Market::expand_supply(Origin::root(), JUSD, 10 * 1_000));
+			assert_eq!(Market::total_issuance(JUSD), 410 * 1_000);
+			assert_eq!(Market::free_balance(JUSD, &Market::staking_pool_account()), 2 * 1_000);
+			assert_eq!(Market::pending_staking_rewards(&ALICE, JUSD), 1_600);
+			assert_eq!(Market::pending_staking_rewards(&BOB, JUSD), 400);
+			let rewarded_event = Event::market(crate::Event::StakersRewarded(JUSD, 2 * 1_000));
+			assert!(System::events().iter().any(|record| record.event == rewarded_event));
+
+			assert_ok!(Market::claim_staking_rewards(Some(ALICE).into(), JUSD));
+			assert_eq!(Market::free_balance(JUSD, &ALICE), 101_600);
+			assert_eq!(Market::pending_staking_rewards(&ALICE, JUSD), 0);
+
+			assert_ok!(Market::stake(Some(BOB).into(), 10));
+			assert_eq!(Market::free_balance(JUSD, &BOB), 100_400);
+			assert_eq!(Market::pending_staking_rewards(&BOB, JUSD), 0);
+
+			assert_ok!(Market::expand_supply(Origin::root(), JUSD, 6 * 1_000));
+			assert_eq!(Market::pending_staking_rewards(&ALICE, JUSD), 800);
+			assert_eq!(Market::pending_staking_rewards(&BOB, JUSD), 400);
+		});
+}
+
+#[test]
+fn unstake_should_pay_out_paused_or_settled_rewards() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			MockPriceSource::set_price(DNAR, Price::saturating_from_integer(4));
+			MockPriceSource::set_price(JUSD, Price::one());
+			assert_ok!(Market::stake(Some(ALICE).into(), 40));
+			assert_ok!(Market::expand_supply(Origin::root(), JUSD, 10 * 1_000));
+			assert_eq!(Market::pending_staking_rewards(&ALICE, JUSD), 2 * 1_000);
+
+			assert_ok!(Market::pause_currency(Origin::root(), JUSD));
+			assert_ok!(Market::unstake(Some(ALICE).into(), 40));
+			assert_eq!(Market::stakes(&ALICE), 0);
+			assert_eq!(Market::free_balance(JUSD, &ALICE), 102 * 1_000);
+			assert_ok!(Market::unpause_currency(Origin::root(), JUSD));
+
+			assert_ok!(Market::stake(Some(ALICE).into(), 40));
+			assert_ok!(Market::expand_supply(Origin::root(), JUSD, 10 * 1_000));
+			assert_ok!(Market::global_settle(Origin::root(), JUSD, 1));
+			assert_ok!(Market::unstake(Some(ALICE).into(), 40));
+			assert_eq!(Market::stakes(&ALICE), 0);
+			assert_eq!(Market::free_balance(JUSD, &ALICE), 104 * 1_000);
+			assert_ok!(Market::transfer_native_currency(Some(ALICE).into(), BOB, 50));
+		});
+}
+
+#[test]
+fn set_savings_rate_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let rate = Price::saturating_from_rational(1, 100);
+		assert_noop!(
+			Market::set_savings_rate(Some(ALICE).into(), JUSD, Some(rate)),
+			BadOrigin
+		);
+		assert_noop!(
+			Market::set_savings_rate(Origin::root(), DNAR, Some(rate)),
+			Error::<Runtime>::NotSerpable
+		);
+
+		assert_ok!(Market::set_savings_rate(Origin::root(), JUSD, Some(rate)));
+		assert_eq!(Market::savings_rate(JUSD), Some(rate));
+		let updated_event = Event::market(crate::Event::SavingsRateUpdated(JUSD, Some(rate)));
+		assert!(System::events().iter().any(|record| record.event == updated_event));
+
+		assert_ok!(Market::set_savings_rate(Origin::root(), JUSD, None));
+		assert_eq!(Market::savings_rate(JUSD), None);
+	});
+}
+
+#[test]
+fn savings_should_accrue_interest() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			MockPriceSource::set_price(DNAR, Price::saturating_from_integer(4));
+			MockPriceSource::set_price(JUSD, Price::one());
+			assert_ok!(Market::set_seigniorage_distribution(
+				Origin::root(),
+				JUSD,
+				vec![(Market::savings_pool_account(), Perbill::from_percent(10))]
+			));
+			assert_ok!(Market::expand_supply(Origin::root(), JUSD, 10 * 1_000));
+			assert_eq!(Market::free_balance(JUSD, &Market::savings_pool_account()), 900);
+			assert_ok!(Market::set_savings_rate(
+				Origin::root(),
+				JUSD,
+				Some(Price::saturating_from_rational(1, 100))
+			));
+
+			assert_noop!(
+				Market::deposit_savings(Some(ALICE).into(), JUSD, 0),
+				Error::<Runtime>::InvalidSavingsAmount
+			);
+			assert_ok!(Market::deposit_savings(Some(ALICE).into(), JUSD, 10 * 1_000));
+			assert_eq!(Market::free_balance(JUSD, &ALICE), 90 * 1_000);
+			assert_eq!(Market::savings_balance(JUSD, &ALICE), 10 * 1_000);
+			assert_eq!(Market::total_savings(JUSD), 10 * 1_000);
+			let deposited_event = Event::market(crate::Event::SavingsDeposited(ALICE, JUSD, 10 * 1_000));
+			assert!(System::events().iter().any(|record| record.event == deposited_event));
+
+			System::set_block_number(3);
+			assert_eq!(Market::savings_of(JUSD, &ALICE), 10_201);
+			assert_noop!(
+				Market::withdraw_savings(Some(ALICE).into(), JUSD, 10_202),
+				Error::<Runtime>::InsufficientSavings
+			);
+			assert_ok!(Market::withdraw_savings(Some(ALICE).into(), JUSD, 10_201));
+			assert_eq!(Market::free_balance(JUSD, &ALICE), 100_201);
+			assert_eq!(Market::savings_balance(JUSD, &ALICE), 0);
+			assert_eq!(Market::total_savings(JUSD), 0);
+			let withdrawn_event = Event::market(crate::Event::SavingsWithdrawn(ALICE, JUSD, 10_201));
+			assert!(System::events().iter().any(|record| record.event == withdrawn_event));
+		});
+}
+
+#[test]
+fn savings_interest_should_be_capped_by_funding() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			MockPriceSource::set_price(DNAR, Price::saturating_from_integer(4));
+			MockPriceSource::set_price(JUSD, Price::one());
+			assert_ok!(Market::set_seigniorage_distribution(
+				Origin::root(),
+				JUSD,
+				vec![(Market::savings_pool_account(), Perbill::from_percent(10))]
+			));
+			assert_ok!(Market::expand_supply(Origin::root(), JUSD, 10 * 1_000));
+			assert_ok!(Market::set_savings_rate(
+				Origin::root(),
+				JUSD,
+				Some(Price::saturating_from_rational(1, 2))
+			));
+			assert_ok!(Market::deposit_savings(Some(ALICE).into(), JUSD, 10 * 1_000));
+
+			System::set_block_number(11);
+			assert_eq!(Market::savings_of(JUSD, &ALICE), 10_900);
+			assert_ok!(Market::withdraw_savings(Some(ALICE).into(), JUSD, 10_900));
+			assert_eq!(Market::free_balance(JUSD, &Market::savings_pool_account()), 0);
+		});
+}
+
+#[test]
+fn savings_should_be_withdrawable_once_settled() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Market::deposit_savings(Some(ALICE).into(), JUSD, 10 * 1_000));
+
+			assert_ok!(Market::pause_currency(Origin::root(), JUSD));
+			assert_noop!(
+				Market::withdraw_savings(Some(ALICE).into(), JUSD, 10 * 1_000),
+				Error::<Runtime>::CurrencyIsPaused
+			);
+			assert_ok!(Market::unpause_currency(Origin::root(), JUSD));
+
+			assert_ok!(Market::global_settle(Origin::root(), JUSD, 1));
+			assert_ok!(Market::withdraw_savings(Some(ALICE).into(), JUSD, 10 * 1_000));
+			assert_eq!(Market::free_balance(JUSD, &ALICE), 100 * 1_000);
+			assert_eq!(Market::savings_balance(JUSD, &ALICE), 0);
+		});
+}
+
+#[test]
+fn set_stability_buffer_shares_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let shares = BufferShares {
+			seigniorage: Perbill::from_percent(10),
+			fees: Perbill::from_percent(50),
+		};
+		assert_noop!(
+			Market::set_stability_buffer_shares(Some(ALICE).into(), JUSD, Some(shares)),
+			BadOrigin
+		);
+		assert_noop!(
+			Market::set_stability_buffer_shares(Origin::root(), DNAR, Some(shares)),
+			Error::<Runtime>::NotSerpable
+		);
+
+		assert_ok!(Market::set_stability_buffer_shares(Origin::root(), JUSD, Some(shares)));
+		assert_eq!(Market::stability_buffer_shares(JUSD), Some(shares));
+		let updated_event = Event::market(crate::Event::StabilityBufferSharesUpdated(JUSD, Some(shares)));
+		assert!(System::events().iter().any(|record| record.event == updated_event));
+
+		assert_ok!(Market::set_stability_buffer_shares(Origin::root(), JUSD, None));
+		assert_eq!(Market::stability_buffer_shares(JUSD), None);
+	});
+}
+
+#[test]
+fn stability_buffer_should_absorb_contractions_first() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			MockPriceSource::set_price(DNAR, Price::saturating_from_integer(4));
+			MockPriceSource::set_price(JUSD, Price::one());
+			assert_ok!(Market::set_stability_buffer_shares(
+				Origin::root(),
+				JUSD,
+				Some(BufferShares {
+					seigniorage: Perbill::from_percent(10),
+					fees: Perbill::from_percent(50),
+				})
+			));
+
+			assert_ok!(Market::expand_supply(Origin::root(), JUSD, 10 * 1_000));
+			assert_eq!(Market::stability_buffer(JUSD), 1_000);
+			assert_eq!(
+				Market::stability_buffer_coverage(JUSD),
+				Some(Price::saturating_from_rational(1_000, 410 * 1_000))
+			);
+			let funded_event = Event::market(crate::Event::StabilityBufferFunded(JUSD, 1_000));
+			assert!(System::events().iter().any(|record| record.event == funded_event));
+
+			assert_ok!(Market::reserve(JUSD, &SERPER, 10 * 1_000));
+			assert_ok!(Market::contract_supply(Origin::root(), JUSD, 600));
+			assert_eq!(Market::stability_buffer(JUSD), 400);
+			assert_eq!(Market::reserved_balance(JUSD, &SERPER), 10 * 1_000);
+
+			assert_ok!(Market::contract_supply(Origin::root(), JUSD, 1_000));
+			assert_eq!(Market::stability_buffer(JUSD), 0);
+			assert_eq!(Market::reserved_balance(JUSD, &SERPER), 9_400);
+			assert_eq!(Market::total_issuance(JUSD), 408_400);
+			let burned_event = Event::market(crate::Event::StabilityBufferBurned(JUSD, 400));
+			assert!(System::events().iter().any(|record| record.event == burned_event));
+		});
+}
+
+#[test]
+fn set_serp_mode_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(Market::serp_mode(JUSD), SerpMode::Direct);
+		assert_noop!(
+			Market::set_serp_mode(Some(ALICE).into(), JUSD, SerpMode::Auction),
+			BadOrigin
+		);
+		assert_noop!(
+			Market::set_serp_mode(Origin::root(), DNAR, SerpMode::Auction),
+			Error::<Runtime>::NotSerpable
+		);
+
+		assert_noop!(
+			Market::set_serp_mode(
+				Origin::root(),
+				JUSD,
+				SerpMode::Dutch(DutchCurve {
+					start_premium: Perbill::from_percent(20),
+					decay: Perbill::from_percent(10),
+					kind: PriceDecay::Linear,
+					min_price: Perbill::zero(),
+				})
+			),
+			Error::<Runtime>::InvalidDutchCurve
+		);
+
+		assert_ok!(Market::set_serp_mode(Origin::root(), JUSD, SerpMode::Auction));
+		assert_eq!(Market::serp_mode(JUSD), SerpMode::Auction);
+		let updated_event = Event::market(crate::Event::SerpModeUpdated(JUSD, SerpMode::Auction));
+		assert!(System::events().iter().any(|record| record.event == updated_event));
+	});
+}
+
+#[test]
+fn expansion_auction_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			MockPriceSource::set_price(DNAR, Price::saturating_from_integer(4));
+			MockPriceSource::set_price(JUSD, Price::one());
+			assert_ok!(Market::set_serp_mode(Origin::root(), JUSD, SerpMode::Auction));
+
+			assert_ok!(Market::expand_supply(Origin::root(), JUSD, 10 * 1_000));
+			assert_eq!(Market::total_issuance(JUSD), 401 * 1_000);
+			let started_event = Event::market(crate::Event::AuctionStarted(0, JUSD, AuctionKind::Expansion, 9_000));
+			assert!(System::events().iter().any(|record| record.event == started_event));
+			assert_eq!(Market::auctions(0).map(|auction| auction.reserve), Some(2));
+			assert_eq!(
+				Market::latest_supply_adjustment(JUSD).map(|a| (a.direction, a.amount)),
+				Some((SerpDirection::Expansion, 1_000))
+			);
+
+			assert_noop!(Market::bid_auction(Some(ALICE).into(), 1, 10), Error::<Runtime>::AuctionNotFound);
+			assert_noop!(Market::bid_auction(Some(ALICE).into(), 0, 0), Error::<Runtime>::InvalidBid);
+			assert_noop!(Market::bid_auction(Some(ALICE).into(), 0, 1), Error::<Runtime>::InvalidBid);
+			assert_ok!(Market::bid_auction(Some(ALICE).into(), 0, 10));
+			assert_eq!(Market::reserved_balance(DNAR, &ALICE), 10);
+			assert_noop!(Market::bid_auction(Some(BOB).into(), 0, 10), Error::<Runtime>::InvalidBid);
+			assert_ok!(Market::bid_auction(Some(BOB).into(), 0, 12));
+			assert_eq!(Market::reserved_balance(DNAR, &ALICE), 0);
+			assert_eq!(Market::reserved_balance(DNAR, &BOB), 12);
+
+			System::set_block_number(9);
+			assert_ok!(Market::bid_auction(Some(ALICE).into(), 0, 20));
+			assert_eq!(Market::reserved_balance(DNAR, &BOB), 0);
+			assert_eq!(Market::auctions(0).map(|auction| auction.end), Some(12));
+
+			Market::on_initialize(11);
+			assert!(Market::auctions(0).is_some());
+			Market::on_initialize(12);
+			assert_eq!(Market::auctions(0), None);
+			assert_eq!(Market::free_balance(DNAR, &ALICE), 80);
+			assert_eq!(Market::reserved_balance(DNAR, &ALICE), 0);
+			assert_eq!(Market::total_issuance(DNAR), 380);
+			assert_eq!(Market::free_balance(JUSD, &ALICE), 109 * 1_000);
+			assert_eq!(Market::total_issuance(JUSD), 410 * 1_000);
+			let settled_event = Event::market(crate::Event::AuctionSettled(0, ALICE, 20));
+			assert!(System::events().iter().any(|record| record.event == settled_event));
+			assert_eq!(
+				Market::latest_supply_adjustment(JUSD).map(|a| (a.direction, a.amount, a.quote_price)),
+				Some((SerpDirection::Expansion, 9_000, 450))
+			);
+		});
+}
+
+#[test]
+fn contraction_auction_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			MockPriceSource::set_price(DNAR, Price::saturating_from_integer(4));
+			MockPriceSource::set_price(JUSD, Price::one());
+			assert_ok!(Market::set_serp_mode(Origin::root(), JUSD, SerpMode::Auction));
+
+			assert_ok!(Market::contract_supply(Origin::root(), JUSD, 40 * 1_000));
+			assert_eq!(Market::total_issuance(JUSD), 400 * 1_000);
+			assert_eq!(Market::auctions(0).map(|auction| auction.reserve), Some(10));
+			assert_eq!(Market::latest_supply_adjustment(JUSD), None);
+
+			assert_noop!(Market::bid_auction(Some(ALICE).into(), 0, 11), Error::<Runtime>::InvalidBid);
+			assert_ok!(Market::bid_auction(Some(ALICE).into(), 0, 10));
+			assert_eq!(Market::reserved_balance(JUSD, &ALICE), 40 * 1_000);
+			assert_noop!(Market::bid_auction(Some(BOB).into(), 0, 10), Error::<Runtime>::InvalidBid);
+			assert_ok!(Market::bid_auction(Some(BOB).into(), 0, 9));
+			assert_eq!(Market::reserved_balance(JUSD, &ALICE), 0);
+			assert_eq!(Market::reserved_balance(JUSD, &BOB), 40 * 1_000);
+
+			Market::on_initialize(11);
+			assert_eq!(Market::auctions(0), None);
+			assert_eq!(Market::free_balance(JUSD, &BOB), 60 * 1_000);
+			assert_eq!(Market::reserved_balance(JUSD, &BOB), 0);
+			assert_eq!(Market::total_issuance(JUSD), 360 * 1_000);
+			assert_eq!(Market::free_balance(DNAR, &BOB), 109);
+			assert_eq!(Market::total_issuance(DNAR), 409);
+			let settled_event = Event::market(crate::Event::AuctionSettled(0, BOB, 9));
+			assert!(System::events().iter().any(|record| record.event == settled_event));
+			let adjustment = Market::latest_supply_adjustment(JUSD).map(|a| (a.direction, a.amount, a.quote_price));
+			assert_eq!(adjustment, Some((SerpDirection::Contraction, 40 * 1_000, 4_444)));
+
+			System::set_block_number(11);
+			let limit = SupplyChangeLimit {
+				max_single_change: Perbill::from_percent(20),
+				max_window_change: Perbill::from_percent(20),
+				window: 100,
+			};
+			assert_ok!(Market::set_supply_change_limit(Origin::root(), JUSD, Some(limit)));
+			assert_ok!(Market::contract_supply(Origin::root(), JUSD, 40 * 1_000));
+			assert_eq!(Market::supply_change_window(JUSD), vec![(11, 40 * 1_000)]);
+			Market::on_initialize(11 + AuctionDuration::get());
+			let aborted_event = Event::market(crate::Event::AuctionAborted(1));
+			assert!(System::events().iter().any(|record| record.event == aborted_event));
+			assert_eq!(Market::total_issuance(JUSD), 360 * 1_000);
+			assert!(Market::supply_change_window(JUSD).is_empty());
+			assert_eq!(
+				Market::latest_supply_adjustment(JUSD).map(|a| (a.direction, a.amount, a.quote_price)),
+				adjustment
+			);
+		});
+}
+
+#[test]
+fn auctions_should_abort_once_paused_or_settled() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			MockPriceSource::set_price(DNAR, Price::saturating_from_integer(4));
+			MockPriceSource::set_price(JUSD, Price::one());
+			assert_ok!(Market::set_serp_mode(Origin::root(), JUSD, SerpMode::Auction));
+
+			assert_ok!(Market::expand_supply(Origin::root(), JUSD, 10 * 1_000));
+			assert_ok!(Market::bid_auction(Some(ALICE).into(), 0, 10));
+			assert_ok!(Market::pause_currency(Origin::root(), JUSD));
+			assert_noop!(
+				Market::bid_auction(Some(BOB).into(), 0, 12),
+				Error::<Runtime>::CurrencyIsPaused
+			);
+			Market::on_initialize(11);
+			assert_eq!(Market::auctions(0), None);
+			assert_eq!(Market::reserved_balance(DNAR, &ALICE), 0);
+			assert_eq!(Market::total_issuance(JUSD), 401 * 1_000);
+			let aborted_event = Event::market(crate::Event::AuctionAborted(0));
+			assert!(System::events().iter().any(|record| record.event == aborted_event));
+			assert_ok!(Market::unpause_currency(Origin::root(), JUSD));
+
+			assert_ok!(Market::expand_supply(Origin::root(), JUSD, 10 * 1_000));
+			assert_ok!(Market::bid_auction(Some(ALICE).into(), 1, 10));
+			assert_ok!(Market::global_settle(Origin::root(), JUSD, 1));
+			assert_eq!(Market::auctions(1), None);
+			assert_eq!(Market::reserved_balance(DNAR, &ALICE), 0);
+			let aborted_event = Event::market(crate::Event::AuctionAborted(1));
+			assert!(System::events().iter().any(|record| record.event == aborted_event));
+		});
+}
+
+#[test]
+fn dutch_expansion_auction_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			MockPriceSource::set_price(DNAR, Price::one());
+			MockPriceSource::set_price(JUSD, Price::one());
+			let curve = DutchCurve {
+				start_premium: Perbill::from_percent(20),
+				decay: Perbill::from_percent(10),
+				kind: PriceDecay::Linear,
+				min_price: Perbill::from_percent(80),
+			};
+			assert_ok!(Market::set_serp_mode(Origin::root(), JUSD, SerpMode::Dutch(curve)));
+
+			assert_ok!(Market::expand_supply(Origin::root(), JUSD, 100 * 1_000));
+			let start_price = Price::saturating_from_rational(12, 10_000);
+			assert_eq!(Market::dutch_auction_price(0), Some(start_price));
+			let started_event = Event::market(crate::Event::DutchAuctionStarted(
+				0,
+				JUSD,
+				AuctionKind::Expansion,
+				90 * 1_000,
+				start_price,
+			));
+			assert!(System::events().iter().any(|record| record.event == started_event));
+
+			assert_noop!(
+				Market::take_auction(Some(ALICE).into(), 0, 91 * 1_000),
+				Error::<Runtime>::InvalidTakeAmount
+			);
+			assert_ok!(Market::take_auction(Some(ALICE).into(), 0, 50 * 1_000));
+			assert_eq!(Market::free_balance(DNAR, &ALICE), 40);
+			assert_eq!(Market::free_balance(JUSD, &ALICE), 150 * 1_000);
+
+			System::set_block_number(3);
+			assert_eq!(
+				Market::dutch_auction_price(0),
+				Some(Price::saturating_from_rational(96, 100_000))
+			);
+			assert_ok!(Market::take_auction(Some(BOB).into(), 0, 40 * 1_000));
+			assert_eq!(Market::free_balance(DNAR, &BOB), 62);
+			assert_eq!(Market::free_balance(JUSD, &BOB), 140 * 1_000);
+			assert_eq!(Market::dutch_auctions(0), None);
+			assert_eq!(Market::total_issuance(JUSD), 500 * 1_000);
+			assert_eq!(Market::total_issuance(DNAR), 302);
+			let taken_event = Event::market(crate::Event::DutchAuctionTaken(0, BOB, 40 * 1_000, 38));
+			assert!(System::events().iter().any(|record| record.event == taken_event));
+			assert_eq!(
+				Market::latest_supply_adjustment(JUSD).map(|a| (a.direction, a.amount, a.quote_price)),
+				Some((SerpDirection::Expansion, 40 * 1_000, 1_052))
+			);
+		});
+}
+
+#[test]
+fn dutch_contraction_auction_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			MockPriceSource::set_price(DNAR, Price::one());
+			MockPriceSource::set_price(JUSD, Price::one());
+			let curve = DutchCurve {
+				start_premium: Perbill::from_percent(20),
+				decay: Perbill::from_percent(50),
+				kind: PriceDecay::Exponential,
+				min_price: Perbill::from_percent(50),
+			};
+			assert_ok!(Market::set_serp_mode(Origin::root(), JUSD, SerpMode::Dutch(curve)));
+
+			assert_ok!(Market::contract_supply(Origin::root(), JUSD, 40 * 1_000));
+			assert_eq!(Market::dutch_auction_price(0), Some(Price::saturating_from_integer(1_200)));
+			assert_ok!(Market::take_auction(Some(ALICE).into(), 0, 24 * 1_000));
+			assert_eq!(Market::free_balance(JUSD, &ALICE), 76 * 1_000);
+			assert_eq!(Market::free_balance(DNAR, &ALICE), 120);
+
+			System::set_block_number(2);
+			assert_eq!(Market::dutch_auction_price(0), Some(Price::saturating_from_integer(600)));
+			assert_ok!(Market::take_auction(Some(BOB).into(), 0, 6 * 1_000));
+			assert_eq!(Market::free_balance(DNAR, &BOB), 110);
+			assert_noop!(
+				Market::take_auction(Some(BOB).into(), 0, 20 * 1_000),
+				Error::<Runtime>::InvalidTakeAmount
+			);
+
+			System::set_block_number(3);
+			assert_eq!(Market::dutch_auction_price(0), None);
+			assert_noop!(
+				Market::take_auction(Some(BOB).into(), 0, 1_000),
+				Error::<Runtime>::AuctionPriceBelowFloor
+			);
+
+			Market::on_initialize(11);
+			assert_eq!(Market::dutch_auctions(0), None);
+			let ended_event = Event::market(crate::Event::DutchAuctionEnded(0, 10 * 1_000));
+			assert!(System::events().iter().any(|record| record.event == ended_event));
+			assert_eq!(Market::total_issuance(JUSD), 370 * 1_000);
+			assert_eq!(Market::total_issuance(DNAR), 430);
+			assert_eq!(
+				Market::latest_supply_adjustment(JUSD).map(|a| (a.direction, a.amount, a.quote_price)),
+				Some((SerpDirection::Contraction, 6 * 1_000, 600))
+			);
+		});
+}
+
+#[test]
+fn dutch_auction_should_end_once_settled() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			MockPriceSource::set_price(DNAR, Price::one());
+			MockPriceSource::set_price(JUSD, Price::one());
+			let curve = DutchCurve {
+				start_premium: Perbill::from_percent(20),
+				decay: Perbill::from_percent(10),
+				kind: PriceDecay::Linear,
+				min_price: Perbill::from_percent(80),
+			};
+			assert_ok!(Market::set_serp_mode(Origin::root(), JUSD, SerpMode::Dutch(curve)));
+			assert_ok!(Market::expand_supply(Origin::root(), JUSD, 100 * 1_000));
+
+			assert_ok!(Market::pause_currency(Origin::root(), JUSD));
+			assert_noop!(
+				Market::take_auction(Some(ALICE).into(), 0, 50 * 1_000),
+				Error::<Runtime>::CurrencyIsPaused
+			);
+			assert_ok!(Market::unpause_currency(Origin::root(), JUSD));
+
+			assert_ok!(Market::global_settle(Origin::root(), JUSD, 1));
+			assert_eq!(Market::dutch_auctions(0), None);
+			let ended_event = Event::market(crate::Event::DutchAuctionEnded(0, 90 * 1_000));
+			assert!(System::events().iter().any(|record| record.event == ended_event));
+			assert_noop!(
+				Market::take_auction(Some(ALICE).into(), 0, 50 * 1_000),
+				Error::<Runtime>::AuctionNotFound
+			);
+		});
+}
+
+#[test]
+fn failed_dutch_auction_start_should_not_redeem_bonds() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			MockPriceSource::set_price(DNAR, Price::one());
+			MockPriceSource::set_price(JUSD, Price::saturating_from_rational(8, 10));
+			assert_ok!(Market::set_peg_target(Origin::root(), JUSD, Some(Price::one())));
+			assert_ok!(Market::buy_bonds(Some(ALICE).into(), JUSD, 8 * 1_000));
+			let curve = DutchCurve {
+				start_premium: Perbill::from_percent(20),
+				decay: Perbill::from_percent(10),
+				kind: PriceDecay::Linear,
+				min_price: Perbill::from_percent(80),
+			};
+			assert_ok!(Market::set_serp_mode(Origin::root(), JUSD, SerpMode::Dutch(curve)));
+
+			// too small a JUSD price in DNAR to auction the lot at
+			MockPriceSource::set_price(DNAR, Price::saturating_from_integer(10_000_000_000_000_000u128));
+			MockPriceSource::set_price(JUSD, Price::saturating_from_rational(11, 10));
+			assert_noop!(
+				Market::expand_supply(Origin::root(), JUSD, 100 * 1_000),
+				Error::<Runtime>::PriceUnavailable
+			);
+			assert_eq!(Market::bond_queue(JUSD), vec![0]);
+		});
+}
+
+#[test]
+fn mint_redeem_fee_should_rise_with_peg_deviation() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(Market::mint_redeem_fee(JUSD), Perbill::from_percent(1));
+		MockPriceSource::set_price(JUSD, Price::one());
+		assert_eq!(Market::mint_redeem_fee(JUSD), Perbill::from_percent(1));
+		MockPriceSource::set_price(JUSD, Price::saturating_from_rational(9, 10));
+		assert_eq!(Market::mint_redeem_fee(JUSD), Perbill::from_percent(6));
+		MockPriceSource::set_price(JUSD, Price::saturating_from_rational(12, 10));
+		assert_eq!(Market::mint_redeem_fee(JUSD), Perbill::from_percent(11));
+	});
+}
+
+#[test]
+fn mint_and_redeem_sett_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_noop!(
+				Market::mint_sett(Some(ALICE).into(), JUSD, 10),
+				Error::<Runtime>::PriceUnavailable
+			);
+			MockPriceSource::set_price(DNAR, Price::one());
+			MockPriceSource::set_price(JUSD, Price::saturating_from_rational(9, 10));
+			assert_noop!(
+				Market::mint_sett(Some(ALICE).into(), DNAR, 10),
+				Error::<Runtime>::NotSerpable
+			);
+			assert_noop!(
+				Market::mint_sett(Some(ALICE).into(), JUSD, 0),
+				Error::<Runtime>::InvalidMintRedeemAmount
+			);
+
+			assert_ok!(Market::mint_sett(Some(ALICE).into(), JUSD, 10));
+			assert_eq!(Market::free_balance(DNAR, &ALICE), 90);
+			assert_eq!(Market::free_balance(JUSD, &ALICE), 109_400);
+			assert_eq!(Market::stability_buffer(JUSD), 600);
+			let minted_event = Event::market(crate::Event::SettMinted(ALICE, JUSD, 10, 9_400, 600));
+			assert!(System::events().iter().any(|record| record.event == minted_event));
+			assert_noop!(
+				Market::mint_sett(Some(ALICE).into(), JUSD, 32),
+				Error::<Runtime>::MintRedeemLimitExceeded
+			);
+
+			assert_ok!(Market::redeem_sett(Some(BOB).into(), JUSD, 20 * 1_000));
+			assert_eq!(Market::free_balance(JUSD, &BOB), 80 * 1_000);
+			assert_eq!(Market::free_balance(DNAR, &BOB), 118);
+			assert_eq!(Market::stability_buffer(JUSD), 1_800);
+			let redeemed_event = Event::market(crate::Event::SettRedeemed(BOB, JUSD, 20 * 1_000, 18, 1_200));
+			assert!(System::events().iter().any(|record| record.event == redeemed_event));
+			assert_eq!(Market::total_issuance(JUSD), 391_200);
+			assert_eq!(Market::total_issuance(DNAR), 408);
+
+			System::set_block_number(2);
+			assert_ok!(Market::mint_sett(Some(ALICE).into(), JUSD, 32));
+			assert_eq!(Market::mint_redeem_volume(JUSD), (2, 32 * 1_000, 0));
+		});
+}
+
+#[test]
+fn swap_sett_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_noop!(
+				Market::swap_sett(Some(ALICE).into(), JUSD, JUSD, 1_000, 0),
+				Error::<Runtime>::InvalidSettSwapPair
+			);
+			assert_noop!(
+				Market::swap_sett(Some(ALICE).into(), DNAR, JUSD, 10, 0),
+				Error::<Runtime>::InvalidSettSwapPair
+			);
+			assert_noop!(
+				Market::swap_sett(Some(ALICE).into(), SETT, JUSD, 10 * 10_000, 0),
+				Error::<Runtime>::PriceUnavailable
+			);
+			MockPriceSource::set_price(SETT, Price::saturating_from_rational(3, 2));
+			MockPriceSource::set_price(JUSD, Price::one());
+			assert_noop!(
+				Market::swap_sett(Some(ALICE).into(), SETT, JUSD, 10 * 10_000, 14_851),
+				Error::<Runtime>::SlippageExceeded
+			);
+
+			assert_ok!(Market::swap_sett(Some(ALICE).into(), SETT, JUSD, 10 * 10_000, 14_850));
+			assert_eq!(Market::free_balance(SETT, &ALICE), 90 * 10_000);
+			assert_eq!(Market::free_balance(JUSD, &ALICE), 114_850);
+			assert_eq!(Market::stability_buffer(SETT), 1_000);
+			assert_eq!(Market::total_issuance(SETT), 3_901_000);
+			assert_eq!(Market::total_issuance(JUSD), 414_850);
+			let swapped_event = Event::market(crate::Event::SettSwapped(
+				ALICE,
+				SETT,
+				JUSD,
+				10 * 10_000,
+				14_850,
+				1_000,
+				Price::saturating_from_rational(1_485, 1_000),
+			));
+			assert!(System::events().iter().any(|record| record.event == swapped_event));
+		});
+}
+
+#[test]
+fn set_basket_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let basket = vec![(JUSD, Perbill::from_percent(60)), (JEUR, Perbill::from_percent(40))];
+		assert_noop!(
+			Market::set_basket(Some(ALICE).into(), SETT, basket.clone()),
+			BadOrigin
+		);
+		assert_noop!(
+			Market::set_basket(
+				Origin::root(),
+				SETT,
+				vec![(JUSD, Perbill::from_percent(60)), (JEUR, Perbill::from_percent(30))]
+			),
+			Error::<Runtime>::InvalidBasket
+		);
+		assert_noop!(
+			Market::set_basket(Origin::root(), SETT, vec![(DNAR, Perbill::one())]),
+			Error::<Runtime>::InvalidBasket
+		);
+		assert_noop!(
+			Market::set_basket(Origin::root(), SETT, vec![(SETT, Perbill::one())]),
+			Error::<Runtime>::InvalidBasket
+		);
+		assert_noop!(
+			Market::set_basket(
+				Origin::root(),
+				SETT,
+				vec![(JUSD, Perbill::from_percent(50)), (JUSD, Perbill::from_percent(50))]
+			),
+			Error::<Runtime>::InvalidBasket
+		);
+
+		assert_ok!(Market::set_basket(Origin::root(), SETT, basket.clone()));
+		assert_eq!(Market::basket(SETT), basket.clone());
+		assert_eq!(Market::base_unit(SETT), 1_000);
+		assert_eq!(Stp258Serp::base_unit(SETT), 1_000);
+		assert_eq!(Market::basket_constituent_count(JUSD), 1);
+		assert_eq!(Market::basket_constituent_count(JEUR), 1);
+		let updated_event = Event::market(crate::Event::BasketUpdated(SETT, basket));
+		assert!(System::events().iter().any(|record| record.event == updated_event));
+
+		assert_noop!(
+			Market::set_basket(Origin::root(), JUSD, vec![(JEUR, Perbill::one())]),
+			Error::<Runtime>::InvalidBasket
+		);
+		assert_noop!(
+			Market::set_basket(Origin::root(), JEUR, vec![(SETT, Perbill::one())]),
+			Error::<Runtime>::InvalidBasket
+		);
+
+		assert_eq!(Market::peg_price(SETT), None);
+		MockPriceSource::set_price(JUSD, Price::one());
+		MockPriceSource::set_price(JEUR, Price::saturating_from_rational(12, 10));
+		assert_eq!(Market::peg_price(SETT), Some(Price::saturating_from_rational(108, 100)));
+
+		assert_ok!(Market::set_basket(Origin::root(), SETT, vec![(JUSD, Perbill::one())]));
+		assert_eq!(Market::basket_constituent_count(JUSD), 1);
+		assert_eq!(Market::basket_constituent_count(JEUR), 0);
+		assert_ok!(Market::set_basket(Origin::root(), JEUR, vec![(JUSD, Perbill::one())]));
+		assert_eq!(Market::basket_constituent_count(JUSD), 2);
+
+		assert_ok!(Market::set_basket(Origin::root(), SETT, vec![]));
+		assert_eq!(Market::basket(SETT), vec![]);
+		assert_eq!(Market::base_unit(SETT), 10_000);
+		assert_eq!(Market::basket_constituent_count(JUSD), 1);
+
+		assert_ok!(Market::update_balance(Origin::root(), ALICE, SETT, 1_000 * 1_000));
+		assert_noop!(
+			Market::set_basket(Origin::root(), SETT, vec![(JUSD, Perbill::one())]),
+			Error::<Runtime>::BaseUnitChange
+		);
+	});
+}
+
+#[test]
+fn on_initialize_should_serp_basket_to_its_peg() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Market::set_basket(
+			Origin::root(),
+			SETT,
+			vec![(JUSD, Perbill::from_percent(60)), (JEUR, Perbill::from_percent(40))]
+		));
+		assert_eq!(Market::base_unit(SETT), 1_000);
+		assert_ok!(Market::update_balance(Origin::root(), ALICE, SETT, 1_000 * 1_000));
+
+		MockPriceSource::set_price(DNAR, Price::saturating_from_integer(4));
+		MockPriceSource::set_price(JUSD, Price::one());
+		MockPriceSource::set_price(JEUR, Price::saturating_from_rational(12, 10));
+		MockPriceSource::set_price(SETT, Price::saturating_from_rational(1_188, 1_000));
+		Market::on_initialize(ADJUSTMENT_FREQUENCY);
+		assert_eq!(Market::total_issuance(SETT), 1_100 * 1_000);
+		let serped_up_event = Event::market(crate::Event::SerpedUpSupply(SETT, 100 * 1_000));
+		assert!(System::events().iter().any(|record| record.event == serped_up_event));
+
+		// A settled basket keeps its base unit, but is no longer serped.
+		assert_ok!(Market::global_settle(Origin::root(), SETT, 1_000));
+		assert_eq!(Market::base_unit(SETT), 1_000);
+		assert_noop!(
+			Market::set_basket(Origin::root(), SETT, vec![]),
+			Error::<Runtime>::CurrencySettled
+		);
+		System::reset_events();
+		Market::on_initialize(2 * ADJUSTMENT_FREQUENCY);
+		assert_eq!(Market::total_issuance(SETT), 1_100 * 1_000);
+		assert!(!System::events()
+			.iter()
+			.any(|record| matches!(record.event, Event::market(crate::Event::SerpFailed(SETT, _)))));
+	});
+}